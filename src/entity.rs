@@ -1,23 +1,62 @@
 //! Custom Bevy ECS bundle for shapes.
 
 use bevy::{
-    asset::Handle,
+    asset::{Asset, Handle},
     ecs::bundle::Bundle,
     render::{
         color::Color,
         draw::{Draw, Visible},
         mesh::Mesh,
-        pipeline::{RenderPipeline, RenderPipelines},
+        pipeline::{PipelineDescriptor, RenderPipeline, RenderPipelines},
         render_graph::base::MainPass,
+        renderer::RenderResources,
     },
-    sprite::QUAD_HANDLE,
+    sprite::{ColorMaterial, QUAD_HANDLE},
     transform::components::{GlobalTransform, Transform},
 };
 use lyon_tessellation::{path::Path, FillOptions};
 
-use crate::{render::SHAPE_PIPELINE_HANDLE, utils::DrawMode};
+use crate::{plugin::MergedShapes, render::SHAPE_PIPELINE_HANDLE, utils::DrawMode};
 
 /// The colors assigned to a shape.
+///
+/// With [`DrawMode::Outlined`], the interior of the shape uses the `main`
+/// color, and its outline uses the `outline` color. Both are stored as vertex
+/// colors in the same mesh.
+///
+/// Since the colors are part of the mesh, shapes don't need any material
+/// asset: all the shapes share the same render pipeline, whatever their
+/// colors, and no asset is allocated per color.
+///
+/// The colors of a spawned shape can be changed by mutating this component.
+/// [`ShapePlugin`](crate::plugin::ShapePlugin) then writes them into the
+/// vertices of the existing mesh, without tessellating the shape again.
+/// Colors coming from a [`Gradient`](crate::gradient::Gradient) or from a
+/// [`VertexModifier`](crate::plugin::VertexModifier) are left untouched.
+///
+/// # Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_prototype_lyon::prelude::*;
+///
+/// fn some_system(mut commands: Commands) {
+///     let circle = shapes::Circle {
+///         radius: 50.0,
+///         ..shapes::Circle::default()
+///     };
+///     commands.spawn_bundle(GeometryBuilder::build_as(
+///         &circle,
+///         ShapeColors::outlined(Color::GOLD, Color::BLACK),
+///         DrawMode::Outlined {
+///             fill_options: FillOptions::default(),
+///             outline_options: StrokeOptions::default().with_line_width(5.0),
+///         },
+///         Transform::default(),
+///     ));
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct ShapeColors {
     /// The main color of the shape. It is the only color for fill and stroke
     /// shapes, and the fill color for the outlined shapes.
@@ -46,7 +85,122 @@ impl ShapeColors {
     }
 }
 
+/// The opacity of a shape and of all its descendants, multiplying the alpha
+/// of their colors.
+///
+/// Insert it as a component on a shape, or on any entity of a hierarchy, to
+/// fade a whole group of shapes at once without tessellating them again. The
+/// opacities of the ancestors of a shape are multiplied together into its
+/// [`GlobalShapeAlpha`]. Entities without a `ShapeAlpha` are fully opaque.
+///
+/// # Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_prototype_lyon::{entity::ShapeAlpha, prelude::*};
+///
+/// fn some_system(mut commands: Commands) {
+///     let circle = shapes::Circle {
+///         radius: 10.0,
+///         ..shapes::Circle::default()
+///     };
+///     commands
+///         .spawn_bundle((Transform::default(), GlobalTransform::default()))
+///         // Both circles are drawn at half opacity.
+///         .insert(ShapeAlpha(0.5))
+///         .with_children(|parent| {
+///             for x in &[-20.0, 20.0] {
+///                 parent.spawn_bundle(GeometryBuilder::build_as(
+///                     &circle,
+///                     ShapeColors::new(Color::BLUE),
+///                     DrawMode::Fill(FillOptions::default()),
+///                     Transform::from_xyz(*x, 0.0, 0.0),
+///                 ));
+///             }
+///         });
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShapeAlpha(pub f32);
+
+impl Default for ShapeAlpha {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+/// The opacity of a shape, computed by the
+/// [`ShapePlugin`](crate::plugin::ShapePlugin) from the [`ShapeAlpha`] of the
+/// shape and of its ancestors, and passed to the shaders as the
+/// `GlobalShapeAlpha_value` uniform.
+#[derive(Debug, Clone, Copy, PartialEq, RenderResources)]
+pub struct GlobalShapeAlpha {
+    /// The product of the opacities of the shape and of its ancestors.
+    pub value: f32,
+}
+
+impl Default for GlobalShapeAlpha {
+    fn default() -> Self {
+        Self { value: 1.0 }
+    }
+}
+
 /// A Bevy `Bundle` to represent a shape.
+///
+/// Like any other Bevy bundle, it can be extended with components inserted in
+/// the same command. For example, inserting a
+/// [`RenderLayers`](bevy::render::camera::RenderLayers) component restricts
+/// the cameras that draw the shape, to separate a minimap from the main view,
+/// or an editor overlay from the scene.
+///
+/// # Example
+///
+/// ```
+/// use bevy::{prelude::*, render::camera::RenderLayers};
+/// use bevy_prototype_lyon::prelude::*;
+///
+/// fn some_system(mut commands: Commands) {
+///     let marker = shapes::Circle {
+///         radius: 5.0,
+///         ..shapes::Circle::default()
+///     };
+///     commands
+///         .spawn_bundle(GeometryBuilder::build_as(
+///             &marker,
+///             ShapeColors::new(Color::RED),
+///             DrawMode::Fill(FillOptions::default()),
+///             Transform::default(),
+///         ))
+///         // Only drawn by the cameras seeing the layer 1.
+///         .insert(RenderLayers::layer(1));
+/// }
+/// ```
+///
+/// The [`Path`] component can be replaced by any system. The shape is then
+/// tessellated again, which allows editing paths live, like in a drawing
+/// application.
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_prototype_lyon::prelude::*;
+/// use lyon_tessellation::path::Path;
+///
+/// // The points placed by the user.
+/// struct Polyline(Vec<Vec2>);
+///
+/// fn update_polylines(mut query: Query<(&Polyline, &mut Path), Changed<Polyline>>) {
+///     for (polyline, mut path) in query.iter_mut() {
+///         let mut path_builder = PathBuilder::new();
+///         if let Some((first, rest)) = polyline.0.split_first() {
+///             path_builder.move_to(*first);
+///             for point in rest {
+///                 path_builder.line_to(*point);
+///             }
+///         }
+///         *path = path_builder.build();
+///     }
+/// }
+/// ```
 #[allow(missing_docs)]
 #[derive(Bundle)]
 pub struct ShapeBundle {
@@ -54,6 +208,7 @@ pub struct ShapeBundle {
     pub mode: DrawMode,
     pub mesh: Handle<Mesh>,
     pub colors: ShapeColors,
+    pub alpha: GlobalShapeAlpha,
     pub main_pass: MainPass,
     pub draw: Draw,
     pub visible: Visible,
@@ -62,6 +217,47 @@ pub struct ShapeBundle {
     pub global_transform: GlobalTransform,
 }
 
+impl ShapeBundle {
+    /// Creates a `ShapeBundle` drawing the given path. The other components
+    /// have their default value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_prototype_lyon::{entity::ShapeBundle, prelude::*};
+    ///
+    /// struct Player;
+    ///
+    /// fn some_system(mut commands: Commands) {
+    ///     let mut path_builder = PathBuilder::new();
+    ///     path_builder.move_to(Vec2::ZERO);
+    ///     path_builder.line_to(Vec2::new(0.0, 20.0));
+    ///     path_builder.line_to(Vec2::new(10.0, 0.0));
+    ///     path_builder.close();
+    ///
+    ///     commands
+    ///         .spawn_bundle(ShapeBundle::new(
+    ///             path_builder.build(),
+    ///             ShapeColors::new(Color::CYAN),
+    ///             DrawMode::Fill(FillOptions::default()),
+    ///             Transform::default(),
+    ///         ))
+    ///         .insert(Player);
+    /// }
+    /// ```
+    #[must_use]
+    pub fn new(path: Path, colors: ShapeColors, mode: DrawMode, transform: Transform) -> Self {
+        Self {
+            path,
+            colors,
+            mode,
+            transform,
+            ..Self::default()
+        }
+    }
+}
+
 impl Default for ShapeBundle {
     fn default() -> Self {
         Self {
@@ -81,8 +277,78 @@ impl Default for ShapeBundle {
                 main: Color::WHITE,
                 outline: Color::BLACK,
             },
+            alpha: GlobalShapeAlpha::default(),
             transform: Transform::default(),
             global_transform: GlobalTransform::default(),
         }
     }
 }
+
+/// A Bevy `Bundle` drawing several shapes with a single mesh, each with its
+/// own [`DrawMode`] and [`ShapeColors`], as described by its
+/// [`MergedShapes`].
+///
+/// Use [`GeometryBuilder::build_merged`](crate::geometry::GeometryBuilder::build_merged)
+/// to create it.
+#[allow(missing_docs)]
+#[derive(Bundle)]
+pub struct MergedShapeBundle {
+    #[bundle]
+    pub shape: ShapeBundle,
+    pub merged: MergedShapes,
+}
+
+/// A Bevy `Bundle` to represent a shape drawn with a material of type `M`,
+/// using a custom render pipeline.
+///
+/// The pipeline receives the same vertex attributes as the default one:
+/// `Vertex_Position_2D` at location 0, `Vertex_Color` at location 1 and
+/// `Vertex_Uv` at location 2. The camera is bound to set 0, the transform to
+/// set 1 and the [`GlobalShapeAlpha`] to binding 1 of set 1, so the material
+/// is usually bound to set 2. Like for any other
+/// material, its render resources must be added to the render graph with an
+/// `AssetRenderResourcesNode<M>`.
+///
+/// Use [`GeometryBuilder::build_with_material`](crate::geometry::GeometryBuilder::build_with_material)
+/// to create it.
+#[allow(missing_docs)]
+#[derive(Bundle)]
+pub struct MaterialShapeBundle<M: Asset> {
+    #[bundle]
+    pub shape: ShapeBundle,
+    pub material: Handle<M>,
+}
+
+impl<M: Asset> MaterialShapeBundle<M> {
+    /// Creates a `MaterialShapeBundle` from a [`ShapeBundle`], replacing its
+    /// render pipeline with the given one.
+    #[must_use]
+    pub fn new(
+        shape: ShapeBundle,
+        material: Handle<M>,
+        pipeline: Handle<PipelineDescriptor>,
+    ) -> Self {
+        Self {
+            shape: ShapeBundle {
+                render_pipelines: RenderPipelines::from_pipelines(vec![RenderPipeline::new(
+                    pipeline,
+                )]),
+                ..shape
+            },
+            material,
+        }
+    }
+}
+
+/// A Bevy `Bundle` to represent a shape filled with a [`ColorMaterial`], which
+/// can have a texture.
+///
+/// By default, the texture is stretched over the bounding box of the path,
+/// and clipped to the shape. Insert a [`UvMapping`](crate::plugin::UvMapping)
+/// component to tile it instead. Its colors are multiplied by the color of the
+/// material and by the [`ShapeColors`], so the shape colors should usually be
+/// white.
+///
+/// Use [`GeometryBuilder::build_textured`](crate::geometry::GeometryBuilder::build_textured)
+/// to create it.
+pub type TexturedShapeBundle = MaterialShapeBundle<ColorMaterial>;
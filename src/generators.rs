@@ -0,0 +1,322 @@
+//! Procedural generation of shapes from data, like contour lines and
+//! Voronoi diagrams.
+//!
+//! The functions in this module return shapes from [`shapes`](crate::shapes),
+//! so they can be drawn separately, or merged into a single entity with a
+//! [`GeometryBuilder`](crate::geometry::GeometryBuilder).
+
+use std::collections::BTreeMap;
+
+use bevy::math::Vec2;
+
+use crate::shapes::{Polygon, Triangle};
+
+/// A corner of a cell of the grid, as column and row indices.
+type Corner = (isize, isize);
+/// An edge of a cell of the grid, as its two corners in ascending order.
+type Edge = (Corner, Corner);
+
+/// Returns the contours of a 2D scalar field at the given iso value, using
+/// the marching squares algorithm.
+///
+/// The field is a grid of `width` columns, stored row by row in `values`, with
+/// the first row at the bottom. The sample at column `x` and row `y` is placed
+/// at `Vec2::new(x, y) * cell_size`, and contours are interpolated linearly
+/// between samples.
+///
+/// The returned polygons enclose the areas where the field is greater than or
+/// equal to `iso_value`. They are always closed, since the field is assumed
+/// to be below the iso value outside of the grid. Outer contours are wound
+/// counter-clockwise and contours of holes are wound clockwise, so adding all
+/// of them to the same [`GeometryBuilder`](crate::geometry::GeometryBuilder)
+/// leaves the holes empty.
+///
+/// # Example
+///
+/// ```
+/// use bevy::math::Vec2;
+/// use bevy_prototype_lyon::generators;
+///
+/// #[rustfmt::skip]
+/// let values = [
+///     0.0, 0.0, 0.0,
+///     0.0, 1.0, 0.0,
+///     0.0, 0.0, 0.0,
+/// ];
+/// let contours = generators::contours(&values, 3, 0.5, Vec2::splat(10.0));
+/// assert_eq!(contours.len(), 1);
+/// ```
+#[must_use]
+// Grid indices are checked against the size of the grid before being used.
+#[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+pub fn contours(values: &[f32], width: usize, iso_value: f32, cell_size: Vec2) -> Vec<Polygon> {
+    if width == 0 {
+        return Vec::new();
+    }
+    let height = values.len() / width;
+    let sample = |(x, y): Corner| {
+        if x < 0 || y < 0 || x >= width as isize || y >= height as isize {
+            f32::NEG_INFINITY
+        } else {
+            values[y as usize * width + x as usize]
+        }
+    };
+
+    // Maps the edge where every contour segment starts to the edge where it
+    // ends, and to its starting point.
+    let mut segments: BTreeMap<Edge, (Edge, Vec2)> = BTreeMap::new();
+    for y in -1..height as isize {
+        for x in -1..width as isize {
+            let corners = [(x, y), (x + 1, y), (x + 1, y + 1), (x, y + 1)];
+            let samples = [
+                sample(corners[0]),
+                sample(corners[1]),
+                sample(corners[2]),
+                sample(corners[3]),
+            ];
+            add_cell_segments(&mut segments, corners, samples, iso_value, cell_size);
+        }
+    }
+
+    link_contours(segments)
+}
+
+/// Returns the Delaunay triangulation of a set of points, using the
+/// Bowyer-Watson algorithm. The triangles are wound counter-clockwise.
+///
+/// Duplicate points are ignored, and nothing is returned if there are less
+/// than three distinct points.
+#[must_use]
+pub fn delaunay(points: &[Vec2]) -> Vec<Triangle> {
+    if points.len() < 3 {
+        return Vec::new();
+    }
+
+    // The first three vertices form a triangle containing all the points,
+    // which is removed at the end. It has to be much larger than the points,
+    // otherwise some triangles on the convex hull go missing.
+    let (min, max) = points
+        .iter()
+        .fold((points[0], points[0]), |(min, max), &point| {
+            (min.min(point), max.max(point))
+        });
+    let center = (min + max) / 2.0;
+    let size = (max - min).max_element().max(1.0) * 1000.0;
+    let mut vertices = vec![
+        center + Vec2::new(-size, -size),
+        center + Vec2::new(size, -size),
+        center + Vec2::new(0.0, size),
+    ];
+    let mut triangles = vec![[0, 1, 2]];
+
+    for (i, &point) in points.iter().enumerate() {
+        if points[..i].contains(&point) {
+            continue;
+        }
+        let index = vertices.len();
+        vertices.push(point);
+
+        let (bad, good): (Vec<[usize; 3]>, Vec<[usize; 3]>) =
+            triangles.iter().partition(|&&[first, second, third]| {
+                in_circumcircle(vertices[first], vertices[second], vertices[third], point)
+            });
+
+        // The edges of the cavity left by the removed triangles are joined
+        // with the new point.
+        triangles = good;
+        for triangle in &bad {
+            for edge in 0..3 {
+                let (from, to) = (triangle[edge], triangle[(edge + 1) % 3]);
+                if !bad.iter().any(|other| has_edge(*other, to, from)) {
+                    triangles.push([from, to, index]);
+                }
+            }
+        }
+    }
+
+    triangles
+        .iter()
+        .filter(|triangle| triangle.iter().all(|&index| index >= 3))
+        .map(|&[first, second, third]| Triangle(vertices[first], vertices[second], vertices[third]))
+        .collect()
+}
+
+/// Returns the Voronoi cells of a set of seed points, clipped to the
+/// rectangle between `min` and `max`. Every point of a cell is closer to its
+/// seed than to any other seed.
+///
+/// There is a cell for every seed, in the same order, wound
+/// counter-clockwise. The cells of seeds outside of the rectangle may be
+/// empty.
+///
+/// # Example
+///
+/// ```
+/// use bevy::math::Vec2;
+/// use bevy_prototype_lyon::generators;
+///
+/// let seeds = [Vec2::new(-50.0, 0.0), Vec2::new(50.0, 0.0)];
+/// let cells = generators::voronoi(&seeds, Vec2::splat(-100.0), Vec2::splat(100.0));
+/// assert_eq!(cells.len(), 2);
+/// ```
+#[must_use]
+pub fn voronoi(seeds: &[Vec2], min: Vec2, max: Vec2) -> Vec<Polygon> {
+    seeds
+        .iter()
+        .map(|&seed| {
+            let mut cell = vec![min, Vec2::new(max.x, min.y), max, Vec2::new(min.x, max.y)];
+            for &other in seeds {
+                if other == seed {
+                    continue;
+                }
+                cell = clip_half_plane(&cell, (seed + other) / 2.0, other - seed);
+                if cell.is_empty() {
+                    break;
+                }
+            }
+
+            Polygon {
+                points: cell,
+                closed: true,
+                holes: Vec::new(),
+            }
+        })
+        .collect()
+}
+
+/// Adds the contour segments crossing a cell of the grid, given its corners
+/// and the samples at the corners, in counter-clockwise order.
+fn add_cell_segments(
+    segments: &mut BTreeMap<Edge, (Edge, Vec2)>,
+    corners: [Corner; 4],
+    samples: [f32; 4],
+    iso_value: f32,
+    cell_size: Vec2,
+) {
+    // The edges crossed by the contour, in counter-clockwise order, and
+    // whether the contour leaves the area when walking along them.
+    let crossings = (0..4)
+        .filter(|&i| (samples[i] >= iso_value) != (samples[(i + 1) % 4] >= iso_value))
+        .map(|i| (i, samples[i] >= iso_value))
+        .collect::<Vec<(usize, bool)>>();
+    let center_inside = samples.iter().sum::<f32>() / 4.0 >= iso_value;
+    let count = crossings.len();
+
+    let edge = |i: usize| {
+        let (start, end) = (corners[i], corners[(i + 1) % 4]);
+        if start < end {
+            (start, end)
+        } else {
+            (end, start)
+        }
+    };
+    let position = |(x, y): Corner| Vec2::new(x as f32, y as f32) * cell_size;
+
+    for (index, &(from, exit)) in crossings.iter().enumerate() {
+        if !exit {
+            continue;
+        }
+        // Saddle cells are disambiguated using the average of the corners:
+        // the inside parts are joined if it is inside.
+        let (to, _) = if count == 4 && !center_inside {
+            crossings[(index + count - 1) % count]
+        } else {
+            crossings[(index + 1) % count]
+        };
+
+        let point = crossing(
+            position(corners[from]),
+            samples[from],
+            position(corners[(from + 1) % 4]),
+            samples[(from + 1) % 4],
+            iso_value,
+        );
+        segments.insert(edge(from), (edge(to), point));
+    }
+}
+
+/// Joins the contour segments into closed polygons.
+fn link_contours(mut segments: BTreeMap<Edge, (Edge, Vec2)>) -> Vec<Polygon> {
+    let mut polygons = Vec::new();
+    while let Some(&start) = segments.keys().next() {
+        let mut points = Vec::new();
+        let mut edge = start;
+        while let Some((next, point)) = segments.remove(&edge) {
+            // Contours along the border of the grid pass through the samples,
+            // which are shared by two segments.
+            if points.last() != Some(&point) {
+                points.push(point);
+            }
+            edge = next;
+        }
+        if points.len() > 1 && points.first() == points.last() {
+            points.pop();
+        }
+        polygons.push(Polygon {
+            points,
+            closed: true,
+            holes: Vec::new(),
+        });
+    }
+
+    polygons
+}
+
+/// Returns the point where the field crosses the iso value between two
+/// samples, one of which is inside and the other outside. Samples outside of
+/// the grid are infinite, so the contour passes through the other sample.
+fn crossing(from: Vec2, from_value: f32, to: Vec2, to_value: f32, iso_value: f32) -> Vec2 {
+    if !from_value.is_finite() {
+        to
+    } else if !to_value.is_finite() {
+        from
+    } else {
+        let t = ((iso_value - from_value) / (to_value - from_value)).clamp(0.0, 1.0);
+        from + (to - from) * t
+    }
+}
+
+/// Tells if `point` is inside the circumcircle of the counter-clockwise
+/// triangle `a`, `b`, `c`. Computed with double precision, since the test is
+/// sensitive to rounding errors.
+fn in_circumcircle(a: Vec2, b: Vec2, c: Vec2, point: Vec2) -> bool {
+    let row = |vertex: Vec2| {
+        let dx = f64::from(vertex.x) - f64::from(point.x);
+        let dy = f64::from(vertex.y) - f64::from(point.y);
+        (dx, dy, dx.mul_add(dx, dy * dy))
+    };
+    let (ax, ay, a_sq) = row(a);
+    let (bx, by, b_sq) = row(b);
+    let (cx, cy, c_sq) = row(c);
+
+    let minor_x = by.mul_add(c_sq, -b_sq * cy);
+    let minor_y = bx.mul_add(c_sq, -b_sq * cx);
+    let minor_sq = bx.mul_add(cy, -by * cx);
+    let det = ax.mul_add(minor_x, a_sq.mul_add(minor_sq, -ay * minor_y));
+    det > 0.0
+}
+
+/// Tells if a triangle has the edge going from `from` to `to`.
+fn has_edge(triangle: [usize; 3], from: usize, to: usize) -> bool {
+    (0..3).any(|k| triangle[k] == from && triangle[(k + 1) % 3] == to)
+}
+
+/// Clips a convex polygon, keeping the part on the opposite side of the
+/// `normal` of the line passing through `origin`.
+fn clip_half_plane(polygon: &[Vec2], origin: Vec2, normal: Vec2) -> Vec<Vec2> {
+    let mut clipped = Vec::with_capacity(polygon.len() + 1);
+    let following = polygon.iter().cycle().skip(1);
+    for (&current, &next) in polygon.iter().zip(following) {
+        let current_side = (current - origin).dot(normal);
+        let next_side = (next - origin).dot(normal);
+        if current_side <= 0.0 {
+            clipped.push(current);
+        }
+        if (current_side <= 0.0) != (next_side <= 0.0) {
+            let t = current_side / (current_side - next_side);
+            clipped.push(current + (next - current) * t);
+        }
+    }
+
+    clipped
+}
@@ -1,10 +1,27 @@
 //! Types for defining and using geometries.
 
-use bevy::transform::components::Transform;
-use lyon_tessellation::path::{path::Builder, Path};
+use bevy::{
+    asset::{Asset, Handle},
+    ecs::{entity::Entity, system::Commands},
+    math::{Vec2, Vec3},
+    render::{color::Color, pipeline::PipelineDescriptor},
+    sprite::ColorMaterial,
+    transform::{components::Transform, hierarchy::BuildChildren},
+};
+use lyon_tessellation::{
+    geom::{CubicBezierSegment, QuadraticBezierSegment},
+    math::{Point, Rect, Size, Vector},
+    path::{path::Builder, Path, PathEvent},
+    FillOptions, StrokeOptions,
+};
 
 use crate::{
-    entity::{ShapeBundle, ShapeColors},
+    entity::{
+        MaterialShapeBundle, MergedShapeBundle, ShapeBundle, ShapeColors, TexturedShapeBundle,
+    },
+    path_ops::{map_points, to_path},
+    plugin::{MergedShape, MergedShapes},
+    render::TEXTURED_SHAPE_PIPELINE_HANDLE,
     utils::DrawMode,
 };
 
@@ -50,9 +67,83 @@ use crate::{
 /// ```
 pub trait Geometry {
     /// Adds the geometry of the shape to the given Lyon path [`Builder`].
+    ///
+    /// A geometry can add any number of sub-paths to the builder. See
+    /// [`DrawMode`] for how multiple sub-paths are filled and stroked.
     fn add_geometry(&self, b: &mut Builder);
 }
 
+/// Defines a parametric shape: a struct with the given fields, a [`Default`]
+/// implementation using the given default values, and a [`Geometry`]
+/// implementation running the given block.
+///
+/// The fields are public. The block gets the shape and the path
+/// [`Builder`] as the two bindings between the bars. The struct derives
+/// `Debug`, `Clone` and `PartialEq`, and other attributes, including derives,
+/// can be added before it.
+///
+/// # Example
+///
+/// ```
+/// use bevy_prototype_lyon::define_shape;
+/// use lyon_tessellation::math::Point;
+///
+/// define_shape! {
+///     /// A rhombus centered on the origin.
+///     #[derive(Copy)]
+///     pub struct Diamond {
+///         /// The length of the horizontal diagonal.
+///         width: f32 = 20.0,
+///         /// The length of the vertical diagonal.
+///         height: f32 = 30.0,
+///     }
+///     |diamond, b| {
+///         let (x, y) = (diamond.width / 2.0, diamond.height / 2.0);
+///         b.begin(Point::new(x, 0.0));
+///         b.line_to(Point::new(0.0, y));
+///         b.line_to(Point::new(-x, 0.0));
+///         b.line_to(Point::new(0.0, -y));
+///         b.close();
+///     }
+/// }
+///
+/// let diamond = Diamond {
+///     height: 40.0,
+///     ..Diamond::default()
+/// };
+/// ```
+#[macro_export]
+macro_rules! define_shape {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $($(#[$field_meta:meta])* $field:ident: $ty:ty = $default:expr),* $(,)?
+        }
+        |$shape:ident, $builder:ident| $body:block
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq)]
+        $vis struct $name {
+            $($(#[$field_meta])* pub $field: $ty),*
+        }
+
+        impl ::core::default::Default for $name {
+            fn default() -> Self {
+                Self {
+                    $($field: $default),*
+                }
+            }
+        }
+
+        impl $crate::geometry::Geometry for $name {
+            fn add_geometry(&self, $builder: &mut $crate::__lyon::path::path::Builder) {
+                let $shape = self;
+                $body
+            }
+        }
+    };
+}
+
 /// This implementation permits to use a Lyon [`Path`] as a [`Geometry`].
 impl Geometry for Path {
     fn add_geometry(&self, b: &mut Builder) {
@@ -60,14 +151,150 @@ impl Geometry for Path {
     }
 }
 
+/// A point of the bounding box of a geometry, used by [`Anchored`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Anchor {
+    /// The center of the bounding box.
+    Center,
+    /// The bottom-left corner of the bounding box.
+    BottomLeft,
+    /// The bottom-right corner of the bounding box.
+    BottomRight,
+    /// The top-right corner of the bounding box.
+    TopRight,
+    /// The top-left corner of the bounding box.
+    TopLeft,
+    /// A point relative to the center of the bounding box, in fractions of
+    /// its size: `(-0.5, -0.5)` is the bottom-left corner, and `(0.5, 0.5)`
+    /// is the top-right one.
+    Custom(Vec2),
+}
+
+impl Anchor {
+    /// Returns the position of the anchor relative to the center of the
+    /// bounding box, in fractions of its size.
+    fn fraction(self) -> Vec2 {
+        match self {
+            Self::Center => Vec2::ZERO,
+            Self::BottomLeft => Vec2::new(-0.5, -0.5),
+            Self::BottomRight => Vec2::new(0.5, -0.5),
+            Self::TopRight => Vec2::new(0.5, 0.5),
+            Self::TopLeft => Vec2::new(-0.5, 0.5),
+            Self::Custom(fraction) => fraction,
+        }
+    }
+}
+
+impl Default for Anchor {
+    fn default() -> Self {
+        Self::Center
+    }
+}
+
+/// A geometry moved so that the [`Anchor`] of its bounding box is at the
+/// origin, where the [`Transform`] of the shape places it.
+///
+/// The shapes of the [`shapes`](crate::shapes) module have different origins:
+/// for example, circles are centered on it, while paths keep their own
+/// coordinates. Anchoring them gives all the shapes a consistent origin,
+/// without computing offsets by hand.
+///
+/// # Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_prototype_lyon::{
+///     geometry::{Anchor, Anchored},
+///     prelude::*,
+/// };
+///
+/// fn some_system(mut commands: Commands) {
+///     let circle = shapes::Circle {
+///         radius: 20.0,
+///         ..shapes::Circle::default()
+///     };
+///     // The top-left corner of the bounding box of the circle is placed at
+///     // the translation of the transform.
+///     commands.spawn_bundle(GeometryBuilder::build_as(
+///         &Anchored {
+///             geometry: circle,
+///             anchor: Anchor::TopLeft,
+///         },
+///         ShapeColors::new(Color::OLIVE),
+///         DrawMode::Fill(FillOptions::default()),
+///         Transform::from_xyz(-100.0, 100.0, 0.0),
+///     ));
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Anchored<G> {
+    /// The geometry to move.
+    pub geometry: G,
+    /// The point of the bounding box of the geometry placed at the origin.
+    pub anchor: Anchor,
+}
+
+impl<G: Geometry> Geometry for Anchored<G> {
+    fn add_geometry(&self, b: &mut Builder) {
+        let path = to_path(&self.geometry);
+        let (min, max) = match tight_bounds(&path) {
+            Some(bounds) => bounds,
+            None => return,
+        };
+        let fraction = self.anchor.fraction() + Vec2::splat(0.5);
+        let anchor = Vector::new(
+            (max.x - min.x).mul_add(fraction.x, min.x),
+            (max.y - min.y).mul_add(fraction.y, min.y),
+        );
+        let path = map_points(&path, |p| p - anchor);
+        b.concatenate(&[path.as_slice()]);
+    }
+}
+
+/// Returns the corners of the bounding box of a path, which only contains the
+/// points of its curves, not their control points.
+fn tight_bounds(path: &Path) -> Option<(Point, Point)> {
+    let mut bounds: Option<(Point, Point)> = None;
+    let mut extend = |rect: Rect| {
+        let (low, high) = (rect.min(), rect.max());
+        bounds = Some(bounds.map_or((low, high), |(min, max)| (min.min(low), max.max(high))));
+    };
+    for event in path.iter() {
+        match event {
+            PathEvent::Begin { at } => extend(Rect::new(at, Size::zero())),
+            PathEvent::Line { to, .. } => extend(Rect::new(to, Size::zero())),
+            PathEvent::Quadratic { from, ctrl, to } => {
+                extend(QuadraticBezierSegment { from, ctrl, to }.bounding_rect());
+            }
+            PathEvent::Cubic {
+                from,
+                ctrl1,
+                ctrl2,
+                to,
+            } => extend(
+                CubicBezierSegment {
+                    from,
+                    ctrl1,
+                    ctrl2,
+                    to,
+                }
+                .bounding_rect(),
+            ),
+            PathEvent::End { .. } => {}
+        }
+    }
+
+    bounds
+}
+
 /// Allows the creation of shapes using geometries added to a path builder.
-pub struct GeometryBuilder(Builder);
+pub struct GeometryBuilder(Builder, Vec<MergedShape>);
 
 impl GeometryBuilder {
     /// Creates a new, empty `GeometryBuilder`.
     #[must_use]
     pub fn new() -> Self {
-        Self(Builder::new())
+        Self(Builder::new(), Vec::new())
     }
 
     /// Adds a geometry to the path builder.
@@ -101,15 +328,181 @@ impl GeometryBuilder {
         self
     }
 
+    /// Adds a geometry with its own colors and draw mode, and with the given
+    /// transform applied to its points, to the shapes drawn by
+    /// [`build_merged`](Self::build_merged).
+    ///
+    /// This allows many shapes to be merged into a single entity, and so
+    /// drawn with a single mesh, while keeping them at different positions.
+    /// The geometries added with [`add`](Self::add) are not part of the merged
+    /// shapes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_prototype_lyon::prelude::*;
+    ///
+    /// fn some_system(mut commands: Commands) {
+    ///     let tile = shapes::RegularPolygon {
+    ///         sides: 6,
+    ///         ..shapes::RegularPolygon::default()
+    ///     };
+    ///     let mut builder = GeometryBuilder::new();
+    ///     for x in 0..100 {
+    ///         for y in 0..100 {
+    ///             let color = if (x + y) % 2 == 0 {
+    ///                 Color::DARK_GREEN
+    ///             } else {
+    ///                 Color::GREEN
+    ///             };
+    ///             let position = Vec3::new(x as f32 * 2.0, y as f32 * 2.0, 0.0);
+    ///             builder.add_transformed(
+    ///                 &tile,
+    ///                 ShapeColors::outlined(color, Color::BLACK),
+    ///                 DrawMode::Outlined {
+    ///                     fill_options: FillOptions::default(),
+    ///                     outline_options: StrokeOptions::default().with_line_width(0.1),
+    ///                 },
+    ///                 Transform::from_translation(position),
+    ///             );
+    ///         }
+    ///     }
+    ///
+    ///     commands.spawn_bundle(builder.build_merged(Transform::default()));
+    /// }
+    /// ```
+    pub fn add_transformed(
+        &mut self,
+        shape: &impl Geometry,
+        colors: ShapeColors,
+        mode: DrawMode,
+        transform: Transform,
+    ) -> &mut Self {
+        let path = map_points(&to_path(shape), |p| {
+            let v = transform.mul_vec3(Vec3::new(p.x, p.y, 0.0));
+            Point::new(v.x, v.y)
+        });
+        self.1.push(MergedShape { path, mode, colors });
+
+        self
+    }
+
+    /// Generates a [`MergedShapeBundle`] drawing the geometries added with
+    /// [`add_transformed`](Self::add_transformed) with a single mesh.
+    #[must_use]
+    pub fn build_merged(self, transform: Transform) -> MergedShapeBundle {
+        let mut builder = Builder::new();
+        for shape in &self.1 {
+            builder.concatenate(&[shape.path.as_slice()]);
+        }
+
+        MergedShapeBundle {
+            shape: ShapeBundle {
+                path: builder.build(),
+                transform,
+                ..ShapeBundle::default()
+            },
+            merged: MergedShapes(self.1),
+        }
+    }
+
     /// Generates a [`ShapeBundle`] using the data contained in the path
     /// builder.
     #[must_use]
     pub fn build(self, colors: ShapeColors, mode: DrawMode, transform: Transform) -> ShapeBundle {
+        ShapeBundle::new(self.0.build(), colors, mode, transform)
+    }
+
+    /// Generates a [`TexturedShapeBundle`] using the data contained in the
+    /// path builder, filled with the given material.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_prototype_lyon::prelude::*;
+    ///
+    /// fn some_system(
+    ///     mut commands: Commands,
+    ///     asset_server: Res<AssetServer>,
+    ///     mut materials: ResMut<Assets<ColorMaterial>>,
+    /// ) {
+    ///     let texture = asset_server.load("textures/wood.png");
+    ///     let hexagon = shapes::RegularPolygon {
+    ///         sides: 6,
+    ///         feature: shapes::RegularPolygonFeature::Radius(100.0),
+    ///         ..shapes::RegularPolygon::default()
+    ///     };
+    ///     let mut builder = GeometryBuilder::new();
+    ///     builder.add(&hexagon);
+    ///
+    ///     commands.spawn_bundle(builder.build_textured(
+    ///         materials.add(texture.into()),
+    ///         DrawMode::Fill(FillOptions::default()),
+    ///         Transform::default(),
+    ///     ));
+    /// }
+    /// ```
+    #[must_use]
+    pub fn build_textured(
+        self,
+        material: Handle<ColorMaterial>,
+        mode: DrawMode,
+        transform: Transform,
+    ) -> TexturedShapeBundle {
+        self.build_with_material(
+            material,
+            TEXTURED_SHAPE_PIPELINE_HANDLE.typed(),
+            mode,
+            transform,
+        )
+    }
+
+    /// Generates a [`MaterialShapeBundle`] using the data contained in the
+    /// path builder, drawn with the given material and render pipeline. The
+    /// [`ShapeColors`] of the shape are white.
+    #[must_use]
+    pub fn build_with_material<M: Asset>(
+        self,
+        material: Handle<M>,
+        pipeline: Handle<PipelineDescriptor>,
+        mode: DrawMode,
+        transform: Transform,
+    ) -> MaterialShapeBundle<M> {
+        MaterialShapeBundle::new(
+            self.build(ShapeColors::new(Color::WHITE), mode, transform),
+            material,
+            pipeline,
+        )
+    }
+
+    /// Generates a [`ShapeBundle`] like [`build`](Self::build), but applies the
+    /// rotation and the scale of `transform` directly to the path, leaving only
+    /// the translation in the [`Transform`] of the bundle.
+    ///
+    /// Since the path is scaled before being tessellated, strokes keep the
+    /// width specified in the [`DrawMode`], instead of being distorted by a
+    /// non-uniform scale.
+    #[must_use]
+    pub fn build_baked(
+        self,
+        colors: ShapeColors,
+        mode: DrawMode,
+        transform: Transform,
+    ) -> ShapeBundle {
+        let path = map_points(&self.0.build(), |p| {
+            let v = transform
+                .rotation
+                .mul_vec3(transform.scale * Vec3::new(p.x, p.y, 0.0));
+            Point::new(v.x, v.y)
+        });
+
         ShapeBundle {
-            path: self.0.build(),
+            path,
             colors,
             mode,
-            transform,
+            transform: Transform::from_translation(transform.translation),
             ..ShapeBundle::default()
         }
     }
@@ -150,3 +543,128 @@ impl Default for GeometryBuilder {
         Self::new()
     }
 }
+
+/// An extension trait of [`Commands`] to spawn shapes in one step.
+pub trait ShapeCommands {
+    /// Spawns an entity with a [`ShapeBundle`] drawing the given geometry, as
+    /// built by [`GeometryBuilder::build_as`], and returns it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_prototype_lyon::prelude::*;
+    ///
+    /// struct Selected;
+    ///
+    /// fn some_system(mut commands: Commands) {
+    ///     let square = shapes::Rectangle {
+    ///         width: 10.0,
+    ///         height: 10.0,
+    ///         ..shapes::Rectangle::default()
+    ///     };
+    ///     let entity = commands.spawn_shape(
+    ///         &square,
+    ///         ShapeColors::new(Color::TEAL),
+    ///         DrawMode::Fill(FillOptions::default()),
+    ///         Transform::default(),
+    ///     );
+    ///     commands.entity(entity).insert(Selected);
+    /// }
+    /// ```
+    fn spawn_shape(
+        &mut self,
+        shape: &impl Geometry,
+        colors: ShapeColors,
+        mode: DrawMode,
+        transform: Transform,
+    ) -> Entity;
+
+    /// Spawns a shape outlined like with [`DrawMode::Outlined`], but as two
+    /// entities: one for the fill, and one for the outline, spawned as its
+    /// child and moved by `outline_z` on the z axis. Returns the fill entity
+    /// and the outline entity.
+    ///
+    /// Since the outline has its own [`ShapeColors`] and [`DrawMode`], it can
+    /// be tinted, hidden or changed independently, for example to highlight a
+    /// selection, while moving with the fill.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_prototype_lyon::prelude::*;
+    ///
+    /// // Marks the outlines to recolor when their shape is selected.
+    /// struct SelectionOutline;
+    ///
+    /// fn some_system(mut commands: Commands) {
+    ///     let circle = shapes::Circle {
+    ///         radius: 20.0,
+    ///         ..shapes::Circle::default()
+    ///     };
+    ///     let (_fill, outline) = commands.spawn_outlined_shape(
+    ///         &circle,
+    ///         ShapeColors::outlined(Color::WHITE, Color::YELLOW),
+    ///         FillOptions::default(),
+    ///         StrokeOptions::default().with_line_width(3.0),
+    ///         Transform::default(),
+    ///         0.1,
+    ///     );
+    ///     commands.entity(outline).insert(SelectionOutline);
+    /// }
+    /// ```
+    fn spawn_outlined_shape(
+        &mut self,
+        shape: &impl Geometry,
+        colors: ShapeColors,
+        fill_options: FillOptions,
+        outline_options: StrokeOptions,
+        transform: Transform,
+        outline_z: f32,
+    ) -> (Entity, Entity);
+}
+
+impl ShapeCommands for Commands<'_> {
+    fn spawn_shape(
+        &mut self,
+        shape: &impl Geometry,
+        colors: ShapeColors,
+        mode: DrawMode,
+        transform: Transform,
+    ) -> Entity {
+        self.spawn_bundle(GeometryBuilder::build_as(shape, colors, mode, transform))
+            .id()
+    }
+
+    fn spawn_outlined_shape(
+        &mut self,
+        shape: &impl Geometry,
+        colors: ShapeColors,
+        fill_options: FillOptions,
+        outline_options: StrokeOptions,
+        transform: Transform,
+        outline_z: f32,
+    ) -> (Entity, Entity) {
+        let path = to_path(shape);
+        let outline = self
+            .spawn_bundle(ShapeBundle::new(
+                path.clone(),
+                ShapeColors::new(colors.outline),
+                DrawMode::Stroke(outline_options),
+                Transform::from_xyz(0.0, 0.0, outline_z),
+            ))
+            .id();
+        let fill = self
+            .spawn_bundle(ShapeBundle::new(
+                path,
+                ShapeColors::new(colors.main),
+                DrawMode::Fill(fill_options),
+                transform,
+            ))
+            .push_children(&[outline])
+            .id();
+
+        (fill, outline)
+    }
+}
@@ -0,0 +1,256 @@
+//! Gradients that can replace the main color of a shape.
+
+use bevy::{math::Vec2, render::color::Color};
+
+use crate::{path_ops::distance_to_segment, utils::Convert};
+
+/// A color at a given position of a [`Gradient`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GradientStop {
+    /// The position of the stop along the gradient, from `0.0` to `1.0`.
+    pub offset: f32,
+    /// The color of the gradient at the stop.
+    pub color: Color,
+}
+
+impl GradientStop {
+    /// Creates a new `GradientStop`.
+    #[must_use]
+    pub const fn new(offset: f32, color: Color) -> Self {
+        Self { offset, color }
+    }
+}
+
+/// A gradient used in place of the main color of a shape, as defined in
+/// [`ShapeColors`](crate::entity::ShapeColors).
+///
+/// It is one of the [optional components](crate::plugin#optional-components)
+/// of a shape. The outline color of outlined shapes is not affected.
+///
+/// The gradient is evaluated at the vertices of the mesh, and the colors are
+/// interpolated linearly in between. Where a linear or a radial gradient
+/// changes color, the triangles of the shape are split until they are small
+/// enough for every stop to show, so even a circle, whose vertices are all on
+/// its outline, gets the colors of the inner stops.
+///
+/// # Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_prototype_lyon::prelude::*;
+///
+/// fn some_system(mut commands: Commands) {
+///     let rectangle = shapes::Rectangle {
+///         width: 200.0,
+///         height: 100.0,
+///         ..shapes::Rectangle::default()
+///     };
+///     commands
+///         .spawn_bundle(GeometryBuilder::build_as(
+///             &rectangle,
+///             ShapeColors::new(Color::WHITE),
+///             DrawMode::Fill(FillOptions::default()),
+///             Transform::default(),
+///         ))
+///         .insert(Gradient::Linear {
+///             start: Vec2::new(-100.0, 0.0),
+///             end: Vec2::new(100.0, 0.0),
+///             stops: vec![
+///                 GradientStop::new(0.0, Color::MIDNIGHT_BLUE),
+///                 GradientStop::new(1.0, Color::TEAL),
+///             ],
+///         });
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum Gradient {
+    /// A gradient changing color along the line from `start` to `end`, in the
+    /// local coordinates of the shape. The color is constant along lines
+    /// perpendicular to it.
+    #[allow(missing_docs)]
+    Linear {
+        start: Vec2,
+        end: Vec2,
+        stops: Vec<GradientStop>,
+    },
+    /// A gradient changing color from `center` outwards, reaching the last
+    /// stop at the given `radius`, in the local coordinates of the shape. The
+    /// color is constant along circles around the center.
+    #[allow(missing_docs)]
+    Radial {
+        center: Vec2,
+        radius: f32,
+        stops: Vec<GradientStop>,
+    },
+    /// A gradient changing color along the length of every sub-path of a
+    /// stroke, from its start to its end. It is meant for
+    /// [`DrawMode::Stroke`](crate::utils::DrawMode::Stroke): fills take the
+    /// color of the first stop.
+    ///
+    /// The stroke is split at the offsets of the stops, so the gradient
+    /// reaches every stop, and changes linearly in between. With a
+    /// [`StrokeDash`](crate::plugin::StrokeDash), every dash is a separate
+    /// sub-path.
+    #[allow(missing_docs)]
+    Stroke { stops: Vec<GradientStop> },
+}
+
+impl Gradient {
+    /// Returns the color of the gradient at the given position, in the local
+    /// coordinates of the shape.
+    #[must_use]
+    pub fn color_at(&self, position: Vec2) -> Color {
+        match self {
+            Self::Linear { start, end, stops } => {
+                let direction = *end - *start;
+                let length_squared = direction.length_squared();
+                let t = if length_squared > 0.0 {
+                    (position - *start).dot(direction) / length_squared
+                } else {
+                    0.0
+                };
+                sample_stops(stops, t)
+            }
+            Self::Radial {
+                center,
+                radius,
+                stops,
+            } => {
+                let t = if *radius > 0.0 {
+                    (position - *center).length() / *radius
+                } else {
+                    0.0
+                };
+                sample_stops(stops, t)
+            }
+            Self::Stroke { stops } => sample_stops(stops, 0.0),
+        }
+    }
+
+    /// Returns the color of the gradient at the given fraction of its length,
+    /// from `0.0` to `1.0`, regardless of its kind.
+    #[must_use]
+    pub fn color_along(&self, fraction: f32) -> Color {
+        sample_stops(self.stops(), fraction)
+    }
+
+    /// Returns the longest edge a triangle can have where the color of the
+    /// gradient changes, for its stops to be sampled finely enough, or `None`
+    /// if the gradient doesn't depend on the position.
+    pub(crate) fn max_edge(&self) -> Option<f32> {
+        let (length, stops) = match self {
+            Self::Linear { start, end, stops } => (start.distance(*end), stops),
+            Self::Radial { radius, stops, .. } => (*radius, stops),
+            Self::Stroke { .. } => return None,
+        };
+        // Close stops need more samples.
+        let gap = stops
+            .windows(2)
+            .map(|pair| pair[1].offset - pair[0].offset)
+            .filter(|gap| *gap > 0.0)
+            .fold(1.0, f32::min);
+        let max_edge = length * (gap / 2.0).max(1.0 / 64.0).min(1.0 / 8.0);
+
+        if max_edge > 0.0 {
+            Some(max_edge)
+        } else {
+            None
+        }
+    }
+
+    /// Tells if the color of the gradient changes inside the given triangle.
+    pub(crate) fn varies_in(&self, triangle: [Vec2; 3]) -> bool {
+        match self {
+            Self::Linear { start, end, .. } => {
+                let direction = *end - *start;
+                let t = |p: &Vec2| (*p - *start).dot(direction) / direction.length_squared();
+                !triangle.iter().all(|p| t(p) < 0.0) && !triangle.iter().all(|p| t(p) > 1.0)
+            }
+            Self::Radial { center, radius, .. } => {
+                triangle_contains(triangle, *center)
+                    || (0..3).any(|i| {
+                        let (from, to) = (triangle[i], triangle[(i + 1) % 3]);
+                        distance_to_segment(center.convert(), from.convert(), to.convert())
+                            < *radius
+                    })
+            }
+            Self::Stroke { .. } => false,
+        }
+    }
+
+    /// Returns the stops of the gradient.
+    #[must_use]
+    pub fn stops(&self) -> &[GradientStop] {
+        match self {
+            Self::Linear { stops, .. } | Self::Radial { stops, .. } | Self::Stroke { stops } => {
+                stops
+            }
+        }
+    }
+}
+
+/// Returns the color of the gradient stops at the given offset. Offsets
+/// outside of the stops take the color of the nearest stop. The stops are
+/// expected to be sorted by offset.
+fn sample_stops(stops: &[GradientStop], offset: f32) -> Color {
+    interpolate_sorted(stops, offset, |stop| (stop.offset, stop.color), lerp_color)
+        .unwrap_or(Color::WHITE)
+}
+
+/// Returns the value at `position` of the piecewise-linear function going
+/// through the given points, made of a position and a value by `point`, and
+/// expected to be sorted by position. Before the first point and after the
+/// last one, the value is constant. Returns `None` without points.
+pub(crate) fn interpolate_sorted<P, T: Copy>(
+    points: &[P],
+    position: f32,
+    point: impl Fn(&P) -> (f32, T),
+    lerp: impl Fn(T, T, f32) -> T,
+) -> Option<T> {
+    let (first, last) = (point(points.first()?), point(points.last()?));
+    if position <= first.0 {
+        return Some(first.1);
+    }
+
+    Some(
+        points
+            .windows(2)
+            .map(|pair| (point(&pair[0]), point(&pair[1])))
+            .find(|(_, to)| position <= to.0)
+            .map_or(last.1, |(from, to)| {
+                let span = to.0 - from.0;
+                let t = if span > 0.0 {
+                    (position - from.0) / span
+                } else {
+                    1.0
+                };
+                lerp(from.1, to.1, t)
+            }),
+    )
+}
+
+/// Tells if a point is inside a triangle, whatever its winding.
+fn triangle_contains(triangle: [Vec2; 3], point: Vec2) -> bool {
+    let side = |from: Vec2, to: Vec2| {
+        let (edge, offset) = (to - from, point - from);
+        edge.x.mul_add(offset.y, -edge.y * offset.x)
+    };
+    let sides = [
+        side(triangle[0], triangle[1]),
+        side(triangle[1], triangle[2]),
+        side(triangle[2], triangle[0]),
+    ];
+
+    sides.iter().all(|side| *side >= 0.0) || sides.iter().all(|side| *side <= 0.0)
+}
+
+/// Interpolates linearly every component of two colors.
+fn lerp_color(from: Color, to: Color, t: f32) -> Color {
+    let lerp = |a: f32, b: f32| (b - a).mul_add(t, a);
+    Color::rgba(
+        lerp(from.r(), to.r()),
+        lerp(from.g(), to.g()),
+        lerp(from.b(), to.b()),
+        lerp(from.a(), to.a()),
+    )
+}
@@ -28,26 +28,68 @@
 //#![allow(clippy::must_use_candidate)]
 
 pub mod entity;
+pub mod generators;
 pub mod geometry;
+pub mod gradient;
+pub mod metrics;
 pub mod path;
+pub mod path_ops;
 pub mod plugin;
 pub mod render;
+pub mod sdf;
 pub mod shapes;
 pub mod utils;
 
+// Used by the `define_shape!` macro.
+#[doc(hidden)]
+pub use lyon_tessellation as __lyon;
+
 /// Import this module as `use bevy_prototype_lyon::prelude::*` to get
 /// convenient imports.
+///
+/// Together with the Bevy prelude, it is enough to draw shapes:
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_prototype_lyon::prelude::*;
+///
+/// fn main() {
+///     App::build()
+///         .add_plugins(DefaultPlugins)
+///         .add_plugin(ShapePlugin)
+///         .add_startup_system(setup.system());
+/// }
+///
+/// fn setup(mut commands: Commands) {
+///     let square = shapes::Rectangle {
+///         width: 50.0,
+///         height: 50.0,
+///         ..shapes::Rectangle::default()
+///     };
+///     commands.spawn_bundle(OrthographicCameraBundle::new_2d());
+///     commands.spawn_shape(
+///         &square,
+///         ShapeColors::outlined(Color::WHITE, Color::BLACK),
+///         DrawMode::Outlined {
+///             fill_options: FillOptions::default(),
+///             outline_options: StrokeOptions::default().with_line_width(2.0),
+///         },
+///         Transform::default(),
+///     );
+/// }
+/// ```
 pub mod prelude {
     pub use lyon_tessellation::{
         FillOptions, FillRule, LineCap, LineJoin, Orientation, StrokeOptions,
     };
 
     pub use crate::{
-        entity::ShapeColors,
-        geometry::{Geometry, GeometryBuilder},
+        entity::{ShapeBundle, ShapeColors},
+        geometry::{Geometry, GeometryBuilder, ShapeCommands},
+        gradient::{Gradient, GradientStop},
         path::PathBuilder,
         plugin::ShapePlugin,
         shapes,
-        utils::DrawMode,
+        utils::{Convert, DefaultDrawMode, DrawMode, FillStyle, ShapeSettings, StrokeStyle},
     };
 }
@@ -0,0 +1,337 @@
+//! Measurements along the outline of a geometry.
+
+use bevy::math::Vec2;
+#[cfg(feature = "text")]
+use lyon_tessellation::path::path::Builder;
+use lyon_tessellation::{math::Point, path::Path};
+
+#[cfg(feature = "text")]
+use crate::shapes::Text;
+use crate::{
+    geometry::Geometry,
+    path_ops::{build_path, flatten, to_path, Polyline},
+    shapes::Line,
+    utils::Convert,
+};
+
+/// A flattened segment of a path, with its position along the path.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct MeasuredSegment {
+    from: Point,
+    to: Point,
+    /// Distance between the start of the path and the start of the segment.
+    start: f32,
+    length: f32,
+    /// Index of the sub-path containing the segment.
+    subpath: usize,
+}
+
+/// Precomputed arc length table of a geometry, used to find points at a given
+/// distance along its outline.
+///
+/// Curves are flattened with the given tolerance, and distances are measured
+/// along the resulting line segments. The sub-paths are measured one after the
+/// other, without accounting for the gaps between them. Closed sub-paths
+/// include their closing segment.
+///
+/// # Example
+///
+/// ```
+/// use bevy::math::Vec2;
+/// use bevy_prototype_lyon::{metrics::PathMetrics, shapes};
+///
+/// let line = shapes::Line(Vec2::ZERO, Vec2::new(10.0, 0.0));
+/// let metrics = PathMetrics::new(&line, 0.01);
+///
+/// assert_eq!(metrics.total_length(), 10.0);
+/// assert_eq!(metrics.point_at(2.5), Some(Vec2::new(2.5, 0.0)));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathMetrics {
+    segments: Vec<MeasuredSegment>,
+    length: f32,
+}
+
+impl PathMetrics {
+    /// Measures the outline of a geometry.
+    #[must_use]
+    pub fn new(geometry: &impl Geometry, tolerance: f32) -> Self {
+        let mut segments = Vec::new();
+        let mut length = 0.0;
+
+        for (subpath, polyline) in flatten(&to_path(geometry), tolerance).iter().enumerate() {
+            let points = &polyline.points;
+            let closing = match (points.first(), points.last()) {
+                (Some(&first), Some(&last)) if polyline.closed && points.len() > 1 => {
+                    Some((last, first))
+                }
+                _ => None,
+            };
+
+            for (from, to) in points.windows(2).map(|w| (w[0], w[1])).chain(closing) {
+                let segment_length = (to - from).length();
+                segments.push(MeasuredSegment {
+                    from,
+                    to,
+                    start: length,
+                    length: segment_length,
+                    subpath,
+                });
+                length += segment_length;
+            }
+        }
+
+        Self { segments, length }
+    }
+
+    /// Returns the length of the whole outline.
+    #[must_use]
+    pub const fn total_length(&self) -> f32 {
+        self.length
+    }
+
+    /// Returns the point at the given distance from the start of the outline.
+    /// The distance is clamped between zero and the total length. Returns
+    /// `None` if the outline is empty.
+    #[must_use]
+    pub fn point_at(&self, distance: f32) -> Option<Vec2> {
+        self.locate(distance)
+            .map(|(segment, t)| segment.from.lerp(segment.to, t).convert())
+    }
+
+    /// Returns the direction of the outline at the given distance from its
+    /// start, as a unit vector. The distance is clamped between zero and the
+    /// total length. Returns `None` if the outline is empty.
+    #[must_use]
+    pub fn tangent_at(&self, distance: f32) -> Option<Vec2> {
+        self.locate(distance).map(|(segment, _)| {
+            let dir = segment.to - segment.from;
+            let length = dir.length();
+            if length > 0.0 {
+                Vec2::new(dir.x, dir.y) / length
+            } else {
+                Vec2::ZERO
+            }
+        })
+    }
+
+    /// Returns the flattened segment of the outline containing the point at
+    /// the given distance from its start. The distance is clamped between zero
+    /// and the total length. Returns `None` if the outline is empty.
+    #[must_use]
+    pub fn segment_at(&self, distance: f32) -> Option<Line> {
+        self.locate(distance)
+            .map(|(segment, _)| Line(segment.from.convert(), segment.to.convert()))
+    }
+
+    /// Returns the part of the outline between two fractions of its total
+    /// length, as open sub-paths. `start` and `end` range from `0.0` to `1.0`,
+    /// while `offset` shifts both of them, wrapping around the end of the
+    /// outline.
+    ///
+    /// Animating `end` from `0.0` to `1.0` gives the effect of the outline
+    /// being drawn over time.
+    #[must_use]
+    pub fn trim(&self, start: f32, end: f32, offset: f32) -> Path {
+        let (start, end) = (start.clamp(0.0, 1.0), end.clamp(0.0, 1.0));
+        if end <= start || self.length <= 0.0 {
+            return Path::new();
+        }
+
+        let shift = offset.rem_euclid(1.0);
+        let mut from = (start + shift) * self.length;
+        let mut to = (end + shift) * self.length;
+        // The whole range can be past the end of the outline.
+        if from >= self.length {
+            from -= self.length;
+            to -= self.length;
+        }
+        let mut polylines = Vec::new();
+        if to > self.length {
+            self.extract(from, self.length, &mut polylines);
+            self.extract(0.0, to - self.length, &mut polylines);
+        } else {
+            self.extract(from, to, &mut polylines);
+        }
+
+        build_path(&polylines)
+    }
+
+    /// Lays out a text along the outline, returning the outlines of its
+    /// glyphs. Requires the `text` feature.
+    ///
+    /// Every glyph is placed with the middle of its baseline on the outline,
+    /// and rotated to follow its direction. The `origin` of the text is
+    /// interpreted relative to the outline: `origin.x` is the distance from
+    /// the start of the outline where the text begins, and `origin.y` moves
+    /// the baseline to the left of the outline. Glyphs that don't fit on the
+    /// outline are left out.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use bevy::math::Vec2;
+    /// use bevy_prototype_lyon::{metrics::PathMetrics, shapes};
+    ///
+    /// let font = std::fs::read("assets/fonts/FiraSans-Bold.ttf").unwrap();
+    /// let badge = shapes::Circle {
+    ///     radius: 100.0,
+    ///     ..shapes::Circle::default()
+    /// };
+    /// let text = shapes::Text {
+    ///     font: &font,
+    ///     text: "Around the circle",
+    ///     size: 24.0,
+    ///     origin: Vec2::ZERO,
+    /// };
+    /// let path = PathMetrics::new(&badge, 0.1).layout_text(&text);
+    /// ```
+    #[cfg(feature = "text")]
+    #[must_use]
+    pub fn layout_text(&self, text: &Text<'_>) -> Path {
+        let mut builder = Builder::new();
+        text.add_glyphs(&mut builder, |pen, advance| {
+            let center = pen.x + advance / 2.0;
+            if center < 0.0 || center > self.length {
+                return None;
+            }
+            let point = self.point_at(center)?;
+            let tangent = self.tangent_at(center)?;
+            let normal = Vec2::new(-tangent.y, tangent.x);
+            Some((point - tangent * (advance / 2.0) + normal * pen.y, tangent))
+        });
+
+        builder.build()
+    }
+
+    /// Adds to `polylines` the part of the outline between two distances from
+    /// its start. A new polyline is started for every sub-path.
+    fn extract(&self, from: f32, to: f32, polylines: &mut Vec<Polyline>) {
+        let mut current: Option<(usize, Vec<Point>)> = None;
+
+        for segment in &self.segments {
+            let segment_end = segment.start + segment.length;
+            if segment.length <= 0.0 || segment_end < from || segment.start > to {
+                continue;
+            }
+
+            let t_from = ((from - segment.start) / segment.length).clamp(0.0, 1.0);
+            let t_to = ((to - segment.start) / segment.length).clamp(0.0, 1.0);
+            let (a, b) = (
+                segment.from.lerp(segment.to, t_from),
+                segment.from.lerp(segment.to, t_to),
+            );
+
+            match current.as_mut() {
+                Some((subpath, points)) if *subpath == segment.subpath => {
+                    if points.last() != Some(&a) {
+                        points.push(a);
+                    }
+                    points.push(b);
+                }
+                _ => {
+                    let previous = current.replace((segment.subpath, vec![a, b]));
+                    if let Some((_, points)) = previous {
+                        polylines.push(Polyline {
+                            points,
+                            closed: false,
+                        });
+                    }
+                }
+            }
+        }
+
+        if let Some((_, points)) = current {
+            polylines.push(Polyline {
+                points,
+                closed: false,
+            });
+        }
+    }
+
+    /// Finds the segment containing the point at the given distance, and the
+    /// position of the point along the segment, between `0.0` and `1.0`.
+    fn locate(&self, distance: f32) -> Option<(&MeasuredSegment, f32)> {
+        // Unlike `clamp`, this doesn't panic when the length is NaN.
+        let distance = distance.max(0.0).min(self.length);
+        let index = match self.segments.binary_search_by(|segment| {
+            segment
+                .start
+                .partial_cmp(&distance)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        }) {
+            Ok(index) => index,
+            Err(index) => index.saturating_sub(1),
+        };
+
+        let segment = self.segments.get(index)?;
+        let t = if segment.length > 0.0 {
+            ((distance - segment.start) / segment.length).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+
+        Some((segment, t))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lyon_tessellation::path::PathEvent;
+
+    use super::*;
+
+    fn endpoints(path: &Path) -> Vec<(f32, f32)> {
+        path.iter()
+            .filter_map(|event| match event {
+                PathEvent::Begin { at } => Some(at),
+                PathEvent::Line { to, .. } => Some(to),
+                _ => None,
+            })
+            .map(|point| (point.x, point.y))
+            .collect()
+    }
+
+    fn assert_close(actual: &[(f32, f32)], expected: &[(f32, f32)]) {
+        assert_eq!(actual.len(), expected.len(), "{:?}", actual);
+        for (a, e) in actual.iter().zip(expected) {
+            assert!(
+                (a.0 - e.0).abs() < 1e-4 && (a.1 - e.1).abs() < 1e-4,
+                "{:?} != {:?}",
+                actual,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn trim_wraps_whole_range_past_the_end() {
+        let line = Line(Vec2::ZERO, Vec2::new(10.0, 0.0));
+        let metrics = PathMetrics::new(&line, 0.01);
+
+        let path = metrics.trim(0.6, 0.9, 0.5);
+
+        assert_close(&endpoints(&path), &[(1.0, 0.0), (4.0, 0.0)]);
+    }
+
+    #[test]
+    fn trim_splits_range_across_the_end() {
+        let line = Line(Vec2::ZERO, Vec2::new(10.0, 0.0));
+        let metrics = PathMetrics::new(&line, 0.01);
+
+        let path = metrics.trim(0.2, 0.6, 0.6);
+
+        assert_close(
+            &endpoints(&path),
+            &[(8.0, 0.0), (10.0, 0.0), (0.0, 0.0), (2.0, 0.0)],
+        );
+    }
+
+    #[test]
+    fn point_at_accepts_non_finite_paths() {
+        let line = Line(Vec2::ZERO, Vec2::new(f32::NAN, 0.0));
+        let metrics = PathMetrics::new(&line, 0.01);
+
+        let _ = metrics.point_at(1.0);
+    }
+}
@@ -3,12 +3,41 @@
 use bevy::math::Vec2;
 use lyon_tessellation::{
     geom::Angle,
-    path::{builder::WithSvg, path::Builder, EndpointId, Path},
+    path::{
+        builder::WithSvg, path::Builder, traits::PathBuilder as _, EndpointId, Path, PathEvent,
+    },
 };
 
-use crate::utils::Convert;
+use crate::{path_ops, utils::Convert};
 
 /// A SVG-like path builder.
+///
+/// The [`Path`] returned by [`build`](Self::build) implements
+/// [`Geometry`](crate::geometry::Geometry), so it can be drawn like any other
+/// shape.
+///
+/// # Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_prototype_lyon::prelude::*;
+///
+/// fn some_system(mut commands: Commands) {
+///     let mut path_builder = PathBuilder::new();
+///     path_builder.move_to(Vec2::ZERO);
+///     path_builder.line_to(Vec2::new(100.0, 0.0));
+///     path_builder.quadratic_bezier_to(Vec2::new(100.0, 100.0), Vec2::new(0.0, 100.0));
+///     path_builder.close();
+///     let path = path_builder.build();
+///
+///     commands.spawn_bundle(GeometryBuilder::build_as(
+///         &path,
+///         ShapeColors::new(Color::ORANGE_RED),
+///         DrawMode::Fill(FillOptions::default()),
+///         Transform::default(),
+///     ));
+/// }
+/// ```
 pub struct PathBuilder(WithSvg<Builder>);
 
 impl PathBuilder {
@@ -24,7 +53,9 @@ impl PathBuilder {
         self.0.build()
     }
 
-    /// Moves the current point to the given position.
+    /// Moves the current point to the given position, starting a new
+    /// sub-path. If a sub-path was already in progress, it is ended without
+    /// being closed.
     pub fn move_to(&mut self, to: Vec2) -> EndpointId {
         self.0.move_to(to.convert())
     }
@@ -74,3 +105,302 @@ impl Default for PathBuilder {
         Self::new()
     }
 }
+
+/// A path builder driven like a turtle: the turtle moves forward in the
+/// direction it is facing, and draws a line if its pen is down. Useful for
+/// procedural content like L-systems and fractals.
+///
+/// The turtle starts at the origin, facing the positive x axis, with its pen
+/// down. Every time the pen is lifted, the current sub-path is ended, so the
+/// [`Path`] returned by [`build`](Self::build) only contains open sub-paths
+/// and is best suited to be stroked.
+///
+/// # Example
+///
+/// ```
+/// use std::f32::consts::FRAC_PI_2;
+///
+/// use bevy_prototype_lyon::path::TurtlePath;
+///
+/// // A square.
+/// let mut turtle = TurtlePath::new();
+/// for _ in 0..4 {
+///     turtle.forward(100.0).turn(FRAC_PI_2);
+/// }
+/// let path = turtle.build();
+/// ```
+pub struct TurtlePath {
+    builder: Builder,
+    position: Vec2,
+    heading: f32,
+    pen_down: bool,
+    drawing: bool,
+    stack: Vec<(Vec2, f32)>,
+}
+
+impl TurtlePath {
+    /// Returns a new `TurtlePath`, with an empty path.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            builder: Builder::new(),
+            position: Vec2::ZERO,
+            heading: 0.0,
+            pen_down: true,
+            drawing: false,
+            stack: Vec::new(),
+        }
+    }
+
+    /// Moves the turtle forward by the given distance, drawing a line if the
+    /// pen is down. A negative distance moves the turtle backwards.
+    pub fn forward(&mut self, distance: f32) -> &mut Self {
+        let to = self.position + Vec2::new(self.heading.cos(), self.heading.sin()) * distance;
+        if self.pen_down {
+            if !self.drawing {
+                self.builder.begin(self.position.convert());
+                self.drawing = true;
+            }
+            self.builder.line_to(to.convert());
+        }
+        self.position = to;
+
+        self
+    }
+
+    /// Turns the turtle counterclockwise by the given angle, in radians. A
+    /// negative angle turns it clockwise.
+    pub fn turn(&mut self, angle: f32) -> &mut Self {
+        self.heading += angle;
+        self
+    }
+
+    /// Lifts the pen, so the turtle moves without drawing.
+    pub fn pen_up(&mut self) -> &mut Self {
+        self.end_subpath();
+        self.pen_down = false;
+        self
+    }
+
+    /// Lowers the pen, so the turtle draws while moving.
+    pub fn pen_down(&mut self) -> &mut Self {
+        self.pen_down = true;
+        self
+    }
+
+    /// Saves the position and the heading of the turtle, so they can be
+    /// restored later with [`pop`](Self::pop).
+    pub fn push(&mut self) -> &mut Self {
+        self.stack.push((self.position, self.heading));
+        self
+    }
+
+    /// Restores the last position and heading saved with
+    /// [`push`](Self::push), without drawing. Does nothing if there is no
+    /// saved state.
+    pub fn pop(&mut self) -> &mut Self {
+        if let Some((position, heading)) = self.stack.pop() {
+            self.end_subpath();
+            self.position = position;
+            self.heading = heading;
+        }
+        self
+    }
+
+    /// Returns the current position of the turtle.
+    #[must_use]
+    pub const fn position(&self) -> Vec2 {
+        self.position
+    }
+
+    /// Returns the direction the turtle is facing, as an angle in radians
+    /// from the positive x axis.
+    #[must_use]
+    pub const fn heading(&self) -> f32 {
+        self.heading
+    }
+
+    /// Returns a finalized [`Path`].
+    #[must_use]
+    pub fn build(mut self) -> Path {
+        self.end_subpath();
+        self.builder.build()
+    }
+
+    fn end_subpath(&mut self) {
+        if self.drawing {
+            self.builder.end(false);
+            self.drawing = false;
+        }
+    }
+}
+
+impl Default for TurtlePath {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The largest absolute value of a coordinate accepted by [`validate`].
+///
+/// Tessellating paths with larger coordinates loses too much precision to
+/// give meaningful results.
+pub const MAX_COORDINATE: f32 = 1.0e7;
+
+/// A problem found in a [`Path`] by [`validate`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PathError {
+    /// A point of the sub-path with the given index is `NaN` or infinite.
+    NonFinite {
+        /// The index of the sub-path.
+        subpath: usize,
+    },
+    /// All the points of the sub-path with the given index are the same.
+    ZeroLength {
+        /// The index of the sub-path.
+        subpath: usize,
+    },
+    /// A coordinate of the sub-path with the given index is greater than
+    /// [`MAX_COORDINATE`] in absolute value.
+    TooLarge {
+        /// The index of the sub-path.
+        subpath: usize,
+        /// The offending coordinate.
+        value: f32,
+    },
+}
+
+impl std::fmt::Display for PathError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NonFinite { subpath } => {
+                write!(f, "sub-path {} has a non-finite coordinate", subpath)
+            }
+            Self::ZeroLength { subpath } => write!(f, "sub-path {} has zero length", subpath),
+            Self::TooLarge { subpath, value } => write!(
+                f,
+                "sub-path {} has a coordinate too large to tessellate: {}",
+                subpath, value
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PathError {}
+
+/// Checks that a path can be tessellated, returning the first problem found.
+///
+/// [`ShapePlugin`](crate::plugin::ShapePlugin) validates every path before
+/// tessellating it. Zero-length sub-paths are removed with
+/// [`remove_zero_length`] and a warning is logged, while paths with other
+/// problems are not drawn and the error is logged instead.
+///
+/// # Errors
+///
+/// Returns a [`PathError`] if a point of the path is not finite, if its
+/// coordinates exceed [`MAX_COORDINATE`], or if a sub-path has all its points
+/// at the same position.
+///
+/// # Example
+///
+/// ```
+/// use bevy::math::Vec2;
+/// use bevy_prototype_lyon::path::{validate, PathBuilder, PathError};
+///
+/// let mut path_builder = PathBuilder::new();
+/// path_builder.move_to(Vec2::ZERO);
+/// path_builder.line_to(Vec2::new(f32::NAN, 0.0));
+///
+/// assert_eq!(
+///     validate(&path_builder.build()),
+///     Err(PathError::NonFinite { subpath: 0 })
+/// );
+/// ```
+pub fn validate(path: &Path) -> Result<(), PathError> {
+    let mut subpath = 0;
+    let mut start = None;
+    let mut moved = false;
+
+    for event in path.iter() {
+        let (points, count) = match event {
+            PathEvent::Begin { at } => {
+                start = Some(at);
+                moved = false;
+                ([at, at, at], 1)
+            }
+            PathEvent::Line { to, .. } => ([to, to, to], 1),
+            PathEvent::Quadratic { ctrl, to, .. } => ([ctrl, to, to], 2),
+            PathEvent::Cubic {
+                ctrl1, ctrl2, to, ..
+            } => ([ctrl1, ctrl2, to], 3),
+            PathEvent::End { .. } => {
+                if !moved {
+                    return Err(PathError::ZeroLength { subpath });
+                }
+                subpath += 1;
+                continue;
+            }
+        };
+
+        for &point in &points[..count] {
+            for &value in &[point.x, point.y] {
+                if !value.is_finite() {
+                    return Err(PathError::NonFinite { subpath });
+                }
+                if value.abs() > MAX_COORDINATE {
+                    return Err(PathError::TooLarge { subpath, value });
+                }
+            }
+            moved |= start != Some(point);
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns a copy of the path without the sub-paths that have all their
+/// points at the same position, which [`validate`] reports as
+/// [`PathError::ZeroLength`].
+///
+/// # Example
+///
+/// ```
+/// use bevy::math::Vec2;
+/// use bevy_prototype_lyon::path::{remove_zero_length, validate, PathBuilder};
+///
+/// let mut path_builder = PathBuilder::new();
+/// path_builder.move_to(Vec2::ZERO);
+/// path_builder.line_to(Vec2::ZERO);
+/// path_builder.move_to(Vec2::ZERO);
+/// path_builder.line_to(Vec2::new(100.0, 0.0));
+///
+/// assert_eq!(validate(&remove_zero_length(&path_builder.build())), Ok(()));
+/// ```
+#[must_use]
+pub fn remove_zero_length(path: &Path) -> Path {
+    let mut builder = Builder::new();
+    for events in path_ops::subpaths(path) {
+        if has_length(&events) {
+            path_ops::add_events(&mut builder, &events);
+        }
+    }
+
+    builder.build()
+}
+
+/// Tells if a sub-path has a point away from its start.
+fn has_length(events: &[PathEvent]) -> bool {
+    let start = match events.first() {
+        Some(&PathEvent::Begin { at }) => at,
+        _ => return false,
+    };
+
+    events.iter().any(|event| match *event {
+        PathEvent::Line { to, .. } => to != start,
+        PathEvent::Quadratic { ctrl, to, .. } => ctrl != start || to != start,
+        PathEvent::Cubic {
+            ctrl1, ctrl2, to, ..
+        } => ctrl1 != start || ctrl2 != start || to != start,
+        PathEvent::Begin { .. } | PathEvent::End { .. } => false,
+    })
+}
@@ -0,0 +1,1243 @@
+//! Operations that create new paths from existing geometries.
+//!
+//! The functions in this module return a Lyon [`Path`], which implements
+//! [`Geometry`], so the result can be drawn like any other shape.
+//!
+//! Curves are flattened into line segments with the given `tolerance` before
+//! being processed, so the resulting paths are only made of straight lines.
+
+use std::collections::HashMap;
+
+use bevy::math::Vec2;
+use lyon_tessellation::{
+    math::{Point, Vector},
+    path::{
+        iterator::PathIterator, path::Builder, traits::PathBuilder, Path, PathEvent,
+        Polygon as LyonPolygon, Winding,
+    },
+};
+
+use crate::{geometry::Geometry, shapes::add_rounded_polyline, utils::Convert};
+
+/// A boolean operation between two shapes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BooleanOp {
+    /// The area covered by either shape.
+    Union,
+    /// The area covered by both shapes.
+    Intersection,
+    /// The area covered by the first shape but not by the second one.
+    Difference,
+    /// The area covered by exactly one of the shapes.
+    Xor,
+}
+
+impl BooleanOp {
+    /// Tells if a point is inside the result, given whether it is inside the
+    /// two operands.
+    const fn apply(self, a: bool, b: bool) -> bool {
+        match self {
+            Self::Union => a || b,
+            Self::Intersection => a && b,
+            Self::Difference => a && !b,
+            Self::Xor => a ^ b,
+        }
+    }
+}
+
+/// A procedural pattern used by [`fill_pattern`].
+///
+/// Patterns are aligned to the origin of the local coordinates of the shape,
+/// so the patterns of adjacent shapes line up.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Pattern {
+    /// Parallel stripes, `width` units wide and `spacing` units apart from
+    /// center to center, rotated counterclockwise by `angle` radians from the
+    /// horizontal. An angle of `FRAC_PI_4` gives a diagonal hatching.
+    #[allow(missing_docs)]
+    Stripes {
+        angle: f32,
+        spacing: f32,
+        width: f32,
+    },
+    /// Two sets of [`Pattern::Stripes`] crossing at a right angle.
+    #[allow(missing_docs)]
+    CrossHatch {
+        angle: f32,
+        spacing: f32,
+        width: f32,
+    },
+    /// Dots of the given `radius`, on a square grid with cells `spacing` units
+    /// wide.
+    #[allow(missing_docs)]
+    Dots { spacing: f32, radius: f32 },
+}
+
+/// A flattened sub-path.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Polyline {
+    pub points: Vec<Point>,
+    pub closed: bool,
+}
+
+/// Builds the Lyon [`Path`] of a geometry.
+pub(crate) fn to_path(geometry: &impl Geometry) -> Path {
+    let mut builder = Builder::new();
+    geometry.add_geometry(&mut builder);
+    builder.build()
+}
+
+/// Flattens a path into a list of polylines, one for each sub-path.
+/// Consecutive duplicate points are removed, as well as the last point of
+/// closed sub-paths when it coincides with the first one.
+pub(crate) fn flatten(path: &Path, tolerance: f32) -> Vec<Polyline> {
+    let mut polylines = Vec::new();
+    let mut points: Vec<Point> = Vec::new();
+
+    for event in path.iter().flattened(tolerance) {
+        match event {
+            PathEvent::Begin { at } => points.push(at),
+            PathEvent::Line { to, .. } => {
+                if points.last() != Some(&to) {
+                    points.push(to);
+                }
+            }
+            PathEvent::End { close, .. } => {
+                if close && points.len() > 1 && points.first() == points.last() {
+                    points.pop();
+                }
+                polylines.push(Polyline {
+                    points: std::mem::take(&mut points),
+                    closed: close,
+                });
+            }
+            // Flattened paths only contain line segments.
+            PathEvent::Quadratic { .. } | PathEvent::Cubic { .. } => {}
+        }
+    }
+
+    polylines
+}
+
+/// Builds a path from a list of polylines.
+pub(crate) fn build_path(polylines: &[Polyline]) -> Path {
+    let mut builder = Builder::new();
+    for polyline in polylines {
+        if let Some((first, rest)) = polyline.points.split_first() {
+            builder.begin(*first);
+            for &point in rest {
+                builder.line_to(point);
+            }
+            builder.end(polyline.closed);
+        }
+    }
+
+    builder.build()
+}
+
+/// Returns a copy of the path with the given function applied to every point,
+/// including the control points of curves. Curves stay exact under affine
+/// transformations.
+pub(crate) fn map_points(path: &Path, f: impl Fn(Point) -> Point) -> Path {
+    let mut builder = Builder::new();
+    for event in path.iter() {
+        match event {
+            PathEvent::Begin { at } => {
+                builder.begin(f(at));
+            }
+            PathEvent::Line { to, .. } => {
+                builder.line_to(f(to));
+            }
+            PathEvent::Quadratic { ctrl, to, .. } => {
+                builder.quadratic_bezier_to(f(ctrl), f(to));
+            }
+            PathEvent::Cubic {
+                ctrl1, ctrl2, to, ..
+            } => {
+                builder.cubic_bezier_to(f(ctrl1), f(ctrl2), f(to));
+            }
+            PathEvent::End { close, .. } => builder.end(close),
+        }
+    }
+
+    builder.build()
+}
+
+/// Returns the winding number of a set of closed polygons around a point.
+pub(crate) fn winding_number(polygons: &[Polyline], p: Point) -> i32 {
+    let mut winding = 0;
+    for polygon in polygons {
+        let next = polygon.points.iter().cycle().skip(1);
+        for (&a, &b) in polygon.points.iter().zip(next) {
+            let side = (b - a).cross(p - a);
+            if a.y <= p.y {
+                if b.y > p.y && side > 0.0 {
+                    winding += 1;
+                }
+            } else if b.y <= p.y && side < 0.0 {
+                winding -= 1;
+            }
+        }
+    }
+
+    winding
+}
+
+/// Returns the union of the two geometries.
+#[must_use]
+pub fn union(a: &impl Geometry, b: &impl Geometry, tolerance: f32) -> Path {
+    boolean(a, b, BooleanOp::Union, tolerance)
+}
+
+/// Returns the intersection of the two geometries.
+#[must_use]
+pub fn intersection(a: &impl Geometry, b: &impl Geometry, tolerance: f32) -> Path {
+    boolean(a, b, BooleanOp::Intersection, tolerance)
+}
+
+/// Returns the area of `a` not covered by `b`.
+#[must_use]
+pub fn difference(a: &impl Geometry, b: &impl Geometry, tolerance: f32) -> Path {
+    boolean(a, b, BooleanOp::Difference, tolerance)
+}
+
+/// Returns the area covered by exactly one of the two geometries.
+#[must_use]
+pub fn xor(a: &impl Geometry, b: &impl Geometry, tolerance: f32) -> Path {
+    boolean(a, b, BooleanOp::Xor, tolerance)
+}
+
+/// Applies a boolean operation to two geometries.
+///
+/// Both geometries are treated as closed areas filled with the non-zero fill
+/// rule. The result is made of closed sub-paths wound counterclockwise, with
+/// holes wound clockwise, so it can be filled with any fill rule.
+#[must_use]
+pub fn boolean(a: &impl Geometry, b: &impl Geometry, op: BooleanOp, tolerance: f32) -> Path {
+    let a = flatten(&to_path(a), tolerance);
+    let b = flatten(&to_path(b), tolerance);
+    let inside = |p: Point| op.apply(winding_number(&a, p) != 0, winding_number(&b, p) != 0);
+
+    build_path(&boundary(edges(&a).chain(edges(&b)), inside, tolerance))
+}
+
+/// Grows the area covered by a geometry by `distance`, or shrinks it if
+/// `distance` is negative. Convex corners are rounded when growing, and
+/// concave corners are rounded when shrinking.
+///
+/// The geometry is treated as a closed area filled with the non-zero fill
+/// rule. Parts of the outline that overlap after the offset are merged, and
+/// parts that vanish are removed.
+#[must_use]
+pub fn offset(geometry: &impl Geometry, distance: f32, tolerance: f32) -> Path {
+    let source = flatten(&to_path(geometry), tolerance);
+
+    // Every polygon is oriented with the inside on the left, so the outward
+    // direction is always on the right.
+    let normalized = boundary(
+        edges(&source),
+        |p| winding_number(&source, p) != 0,
+        tolerance,
+    );
+    if distance == 0.0 {
+        return build_path(&normalized);
+    }
+
+    let raw = normalized
+        .iter()
+        .filter_map(|polygon| offset_polygon(&polygon.points, distance, tolerance))
+        .collect::<Vec<Polyline>>();
+    build_path(&boundary(
+        edges(&raw),
+        |p| winding_number(&raw, p) > 0,
+        tolerance,
+    ))
+}
+
+/// Fills the area of a geometry with a procedural [`Pattern`], returning the
+/// parts of the pattern inside it. Filling the result on top of the shape,
+/// with a different color, makes the shape distinguishable without relying on
+/// colors alone.
+///
+/// The geometry is treated as a closed area filled with the non-zero fill
+/// rule. If the spacing of the pattern is not positive, the result is empty.
+///
+/// # Example
+///
+/// ```
+/// use std::f32::consts::FRAC_PI_4;
+///
+/// use bevy_prototype_lyon::{
+///     path_ops::{self, Pattern},
+///     shapes,
+/// };
+///
+/// let square = shapes::Rectangle {
+///     width: 100.0,
+///     height: 100.0,
+///     ..shapes::Rectangle::default()
+/// };
+/// let hatching = path_ops::fill_pattern(
+///     &square,
+///     Pattern::Stripes {
+///         angle: FRAC_PI_4,
+///         spacing: 10.0,
+///         width: 2.0,
+///     },
+///     0.1,
+/// );
+/// ```
+#[must_use]
+pub fn fill_pattern(geometry: &impl Geometry, pattern: Pattern, tolerance: f32) -> Path {
+    let shape = flatten(&to_path(geometry), tolerance);
+    let tiles = flatten(&pattern_tiles(&shape, pattern), tolerance);
+    let inside = |p: Point| winding_number(&shape, p) != 0 && winding_number(&tiles, p) != 0;
+
+    build_path(&boundary(
+        edges(&shape).chain(edges(&tiles)),
+        inside,
+        tolerance,
+    ))
+}
+
+/// Builds the tiles of a pattern covering the bounding box of the polygons.
+fn pattern_tiles(polygons: &[Polyline], pattern: Pattern) -> Path {
+    let mut builder = Builder::new();
+    let mut points = polygons.iter().flat_map(|polygon| polygon.points.iter());
+    let (min, max) = match points.next() {
+        Some(&first) => points.fold((first, first), |(min, max), &p| (min.min(p), max.max(p))),
+        None => return builder.build(),
+    };
+
+    match pattern {
+        Pattern::Stripes {
+            angle,
+            spacing,
+            width,
+        } => add_stripes(&mut builder, (min, max), angle, spacing, width),
+        Pattern::CrossHatch {
+            angle,
+            spacing,
+            width,
+        } => {
+            add_stripes(&mut builder, (min, max), angle, spacing, width);
+            add_stripes(
+                &mut builder,
+                (min, max),
+                angle + std::f32::consts::FRAC_PI_2,
+                spacing,
+                width,
+            );
+        }
+        Pattern::Dots { spacing, radius } if spacing > 0.0 => {
+            let cells = |from: f32, to: f32| {
+                ((from - radius) / spacing).floor() as i32..=((to + radius) / spacing).ceil() as i32
+            };
+            for i in cells(min.x, max.x) {
+                for j in cells(min.y, max.y) {
+                    let center = Point::new(i as f32 * spacing, j as f32 * spacing);
+                    builder.add_circle(center, radius, Winding::Positive);
+                }
+            }
+        }
+        Pattern::Dots { .. } => {}
+    }
+
+    builder.build()
+}
+
+/// Adds to the builder the stripes of a [`Pattern::Stripes`] covering the
+/// given bounding box.
+fn add_stripes(
+    builder: &mut Builder,
+    (min, max): (Point, Point),
+    angle: f32,
+    spacing: f32,
+    width: f32,
+) {
+    if spacing <= 0.0 {
+        return;
+    }
+
+    let direction = Vector::new(angle.cos(), angle.sin());
+    let normal = Vector::new(-direction.y, direction.x);
+    let center = min.lerp(max, 0.5);
+    let radius = (max - min).length() / 2.0;
+    let along = direction * radius;
+    let across = normal * (width / 2.0);
+
+    let middle = center.to_vector().dot(normal) / spacing;
+    let count = (radius / spacing).ceil() + 1.0;
+    for index in (middle - count).floor() as i32..=(middle + count).ceil() as i32 {
+        // The point of the stripe closest to the center of the bounding box.
+        let offset = (index as f32).mul_add(spacing, -center.to_vector().dot(normal));
+        let origin = center + normal * offset;
+        builder.add_polygon(LyonPolygon {
+            points: &[
+                origin - along - across,
+                origin + along - across,
+                origin + along + across,
+                origin - along + across,
+            ],
+            closed: true,
+        });
+    }
+}
+
+/// Reduces the number of vertices of a geometry, using the
+/// Ramer–Douglas–Peucker algorithm. Curves are flattened first, then every
+/// vertex that is closer than `tolerance` to the simplified outline is
+/// removed.
+#[must_use]
+pub fn simplify(geometry: &impl Geometry, tolerance: f32) -> Path {
+    let polylines = flatten(&to_path(geometry), tolerance)
+        .into_iter()
+        .map(|polyline| {
+            if polyline.closed && polyline.points.len() > 3 {
+                // Closed polylines are simplified as open ones ending where
+                // they start, so the first point is always kept.
+                let mut points = polyline.points;
+                points.push(points[0]);
+                let mut points = douglas_peucker(&points, tolerance);
+                points.pop();
+                Polyline {
+                    points,
+                    closed: true,
+                }
+            } else {
+                Polyline {
+                    points: douglas_peucker(&polyline.points, tolerance),
+                    closed: polyline.closed,
+                }
+            }
+        })
+        .collect::<Vec<Polyline>>();
+
+    build_path(&polylines)
+}
+
+/// Replaces the corners of a geometry with circular arcs of the given radius.
+/// The radius is reduced where the edges are too short to fit it, and the
+/// endpoints of open sub-paths are kept sharp.
+///
+/// Curves are flattened first, so you may want to [`simplify`] geometries with
+/// curved parts before rounding their corners.
+#[must_use]
+pub fn round_corners(geometry: &impl Geometry, radius: f32, tolerance: f32) -> Path {
+    let mut builder = Builder::new();
+    for polyline in flatten(&to_path(geometry), tolerance) {
+        let points = polyline
+            .points
+            .iter()
+            .map(|&p| p.convert())
+            .collect::<Vec<Vec2>>();
+        add_rounded_polyline(&mut builder, &points, radius, polyline.closed);
+    }
+
+    builder.build()
+}
+
+/// Blends two geometries, returning `a` when `t` is `0.0` and `b` when `t` is
+/// `1.0`. Animating `t` morphs a shape into the other one.
+///
+/// The sub-paths of the two geometries are paired by their order, and extra
+/// sub-paths are ignored. Paired sub-paths are resampled at the same
+/// fractions of their length, so they can have a different number of
+/// vertices. The first vertex of each sub-path is matched with the first
+/// vertex of the other one.
+#[must_use]
+pub fn interpolate(a: &impl Geometry, b: &impl Geometry, t: f32, tolerance: f32) -> Path {
+    let a = flatten(&to_path(a), tolerance);
+    let b = flatten(&to_path(b), tolerance);
+
+    let polylines = a
+        .iter()
+        .zip(b.iter())
+        .map(|(a, b)| {
+            let closed = a.closed && b.closed;
+            let a_stops = arc_fractions(&a.points, closed);
+            let b_stops = arc_fractions(&b.points, closed);
+
+            // Sampling at the vertices of both polylines preserves the
+            // vertices of `a` when `t` is zero, and those of `b` when it is one.
+            let mut stops = a_stops
+                .iter()
+                .chain(b_stops.iter())
+                .copied()
+                .collect::<Vec<f32>>();
+            stops.sort_by(|x, y| x.partial_cmp(y).unwrap_or(std::cmp::Ordering::Equal));
+            stops.dedup_by(|x, y| (*x - *y).abs() <= f32::EPSILON);
+
+            let points = stops
+                .iter()
+                .map(|&stop| {
+                    let from = sample_polyline(&a.points, &a_stops, closed, stop);
+                    let to = sample_polyline(&b.points, &b_stops, closed, stop);
+                    from.lerp(to, t)
+                })
+                .collect();
+
+            Polyline { points, closed }
+        })
+        .collect::<Vec<Polyline>>();
+
+    build_path(&polylines)
+}
+
+/// Returns the signed area of every sub-path of a geometry, in order. The area
+/// is positive for counter-clockwise sub-paths and negative for clockwise
+/// ones, and it is zero for open sub-paths.
+#[must_use]
+pub fn signed_areas(geometry: &impl Geometry, tolerance: f32) -> Vec<f32> {
+    flatten(&to_path(geometry), tolerance)
+        .iter()
+        .map(|polyline| {
+            if polyline.closed {
+                polygon_area(&polyline.points)
+            } else {
+                0.0
+            }
+        })
+        .collect()
+}
+
+/// Returns the path of a geometry with the direction of every sub-path
+/// reversed. Curves are kept as they are, and the sub-paths stay in the same
+/// order.
+#[must_use]
+pub fn reverse(geometry: &impl Geometry) -> Path {
+    let mut builder = Builder::new();
+    for events in subpaths(&to_path(geometry)) {
+        add_reversed(&mut builder, &events);
+    }
+
+    builder.build()
+}
+
+/// Returns the path of a geometry with consistent winding: outer sub-paths
+/// are counter-clockwise, the holes inside them are clockwise, the islands
+/// inside the holes are counter-clockwise again, and so on. Open sub-paths
+/// are left untouched.
+///
+/// The nesting of a sub-path is determined from its first point, so
+/// sub-paths are expected not to cross each other. Curves are kept as they
+/// are, and are only flattened with the given tolerance to measure the
+/// sub-paths.
+#[must_use]
+pub fn normalize_winding(geometry: &impl Geometry, tolerance: f32) -> Path {
+    let path = to_path(geometry);
+    let polylines = flatten(&path, tolerance);
+
+    let mut builder = Builder::new();
+    for (index, (events, polyline)) in subpaths(&path).iter().zip(&polylines).enumerate() {
+        let first = match polyline.points.first() {
+            Some(&first) if polyline.closed && polyline.points.len() > 2 => first,
+            _ => {
+                add_events(&mut builder, events);
+                continue;
+            }
+        };
+
+        let depth = polylines
+            .iter()
+            .enumerate()
+            .filter(|&(other_index, other)| {
+                other_index != index
+                    && other.closed
+                    && winding_number(std::slice::from_ref(other), first) != 0
+            })
+            .count();
+        let counter_clockwise = polygon_area(&polyline.points) > 0.0;
+        if counter_clockwise == (depth % 2 == 0) {
+            add_events(&mut builder, events);
+        } else {
+            add_reversed(&mut builder, events);
+        }
+    }
+
+    builder.build()
+}
+
+/// The largest number of times [`dash`] repeats its pattern along an outline.
+pub const MAX_DASH_PERIODS: f32 = 10_000.0;
+
+/// Splits the outline of a geometry into dashes, returning them as open
+/// sub-paths. Stroking the result gives a dashed outline.
+///
+/// `pattern` alternates the lengths of the dashes and of the gaps between
+/// them, starting with a dash. If it has an odd number of elements, it is
+/// repeated to make it even, like in SVG. `offset` shifts the pattern
+/// backwards along the outline. The pattern restarts at the beginning of every
+/// sub-path, and dashes of zero length are skipped.
+///
+/// If the pattern is empty, has negative elements, or has a total length of
+/// zero, the outline is returned undashed. So is an outline that would repeat
+/// the pattern more than [`MAX_DASH_PERIODS`] times, since it would be too
+/// expensive to split.
+///
+/// # Example
+///
+/// ```
+/// use bevy::math::Vec2;
+/// use bevy_prototype_lyon::{path_ops, shapes};
+///
+/// let line = shapes::Line(Vec2::ZERO, Vec2::new(100.0, 0.0));
+/// // 10 units long dashes, separated by 5 units long gaps.
+/// let dashed = path_ops::dash(&line, &[10.0, 5.0], 0.0, 0.1);
+/// ```
+#[must_use]
+pub fn dash(geometry: &impl Geometry, pattern: &[f32], offset: f32, tolerance: f32) -> Path {
+    let polylines = flatten(&to_path(geometry), tolerance);
+    let pattern = if pattern.len() % 2 == 1 {
+        pattern.repeat(2)
+    } else {
+        pattern.to_vec()
+    };
+    let period = pattern.iter().sum::<f32>();
+    if !period.is_finite() || period <= 0.0 || pattern.iter().any(|&length| length < 0.0) {
+        return build_path(&polylines);
+    }
+
+    let outlines: Vec<(Vec<Point>, Vec<f32>)> = polylines.iter().map(walk).collect();
+    let total_length: f32 = outlines
+        .iter()
+        .filter_map(|(_, distances)| distances.last())
+        .sum();
+    if !total_length.is_finite() || total_length / period > MAX_DASH_PERIODS {
+        return build_path(&polylines);
+    }
+
+    let mut dashes = Vec::new();
+    for (points, distances) in &outlines {
+        let length = distances.last().copied().unwrap_or(0.0);
+        let mut cursor = PolylineCursor {
+            points,
+            distances,
+            index: 1,
+        };
+
+        let mut start = -offset.rem_euclid(period);
+        for (index, &dash_length) in pattern.iter().enumerate().cycle() {
+            if start >= length {
+                break;
+            }
+            let end = start + dash_length;
+            if dash_length > 0.0 && end <= start {
+                // The dashes are too short to make progress along the outline.
+                break;
+            }
+            let (from, to) = (start.max(0.0), end.min(length));
+            if index % 2 == 0 && from < to {
+                dashes.push(Polyline {
+                    points: cursor.extract(from, to),
+                    closed: false,
+                });
+            }
+            start = end;
+        }
+    }
+
+    build_path(&dashes)
+}
+
+/// Flattens a path, adding a point at each of the given fractions of the
+/// length of every sub-path, so a stroke of the result has vertices there. The
+/// fractions are expected to be sorted.
+pub(crate) fn split_at_fractions(path: &Path, fractions: &[f32], tolerance: f32) -> Path {
+    let polylines: Vec<Polyline> = flatten(path, tolerance)
+        .into_iter()
+        .map(|polyline| {
+            let (points, distances) = walk(&polyline);
+            let length = distances.last().copied().unwrap_or(0.0);
+
+            let mut split = Vec::with_capacity(points.len() + fractions.len());
+            for (i, pair) in points.windows(2).enumerate() {
+                let (from, to) = (distances[i], distances[i + 1]);
+                split.push(pair[0]);
+                split.extend(
+                    fractions
+                        .iter()
+                        .map(|fraction| fraction * length)
+                        .filter(|&distance| distance > from && distance < to)
+                        .map(|distance| pair[0].lerp(pair[1], (distance - from) / (to - from))),
+                );
+            }
+            // Closed polylines end with a copy of their first point.
+            if !polyline.closed {
+                split.extend(points.last());
+            }
+
+            Polyline {
+                points: split,
+                closed: polyline.closed,
+            }
+        })
+        .collect();
+
+    build_path(&polylines)
+}
+
+/// Returns the points met when walking along a polyline, ending with its first
+/// point if it is closed, with the distance of every one of them from the
+/// start.
+fn walk(polyline: &Polyline) -> (Vec<Point>, Vec<f32>) {
+    let mut points = polyline.points.clone();
+    if polyline.closed && points.len() > 1 {
+        points.push(points[0]);
+    }
+    let distances = distances(&points);
+    (points, distances)
+}
+
+/// Returns the distance of every vertex from the start of a polyline.
+fn distances(points: &[Point]) -> Vec<f32> {
+    let mut distance = 0.0;
+    let mut distances = Vec::with_capacity(points.len());
+    for (i, &point) in points.iter().enumerate() {
+        if i > 0 {
+            distance += (point - points[i - 1]).length();
+        }
+        distances.push(distance);
+    }
+
+    distances
+}
+
+/// Walks along a polyline with at least two points, given the distance of
+/// every vertex from its start, to extract parts of it at increasing distances
+/// without going over the vertices it already passed.
+struct PolylineCursor<'a> {
+    points: &'a [Point],
+    distances: &'a [f32],
+    /// The first vertex after the last extracted distance, or the last vertex.
+    index: usize,
+}
+
+impl PolylineCursor<'_> {
+    /// Returns the part of the polyline between two distances from its start.
+    /// `from` must not be smaller than the end of the previous part.
+    fn extract(&mut self, from: f32, to: f32) -> Vec<Point> {
+        self.seek(from);
+        let mut extracted = vec![self.point_at(from)];
+        extracted.extend(
+            self.points[self.index..]
+                .iter()
+                .zip(&self.distances[self.index..])
+                .skip_while(|&(_, &d)| d <= from)
+                .take_while(|&(_, &d)| d < to)
+                .map(|(&point, _)| point),
+        );
+        self.seek(to);
+        extracted.push(self.point_at(to));
+
+        extracted
+    }
+
+    /// Moves the cursor to the first vertex at `distance` or after it.
+    fn seek(&mut self, distance: f32) {
+        while self.index < self.distances.len() - 1 && self.distances[self.index] < distance {
+            self.index += 1;
+        }
+    }
+
+    /// Returns the point at `distance` from the start, on the segment ending
+    /// at the current vertex.
+    fn point_at(&self, distance: f32) -> Point {
+        let (start, end) = (self.distances[self.index - 1], self.distances[self.index]);
+        let span = end - start;
+        let t = if span > 0.0 {
+            ((distance - start) / span).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        self.points[self.index - 1].lerp(self.points[self.index], t)
+    }
+}
+
+/// Returns the distance of every vertex from the start of a polyline, as a
+/// fraction of its total length.
+fn arc_fractions(points: &[Point], closed: bool) -> Vec<f32> {
+    let mut distances = distances(points);
+    let distance = distances.last().copied().unwrap_or(0.0);
+    let total = match (points.first(), points.last()) {
+        (Some(&first), Some(&last)) if closed => distance + (first - last).length(),
+        _ => distance,
+    };
+    if total > 0.0 {
+        distances.iter_mut().for_each(|d| *d /= total);
+    }
+
+    distances
+}
+
+/// Returns the point at the given fraction of the length of a polyline, using
+/// the fractions computed by [`arc_fractions`].
+fn sample_polyline(points: &[Point], fractions: &[f32], closed: bool, stop: f32) -> Point {
+    let index = fractions
+        .iter()
+        .rposition(|&fraction| fraction <= stop)
+        .unwrap_or(0);
+    let from = points[index];
+    let (to, next_fraction) = match fractions.get(index + 1) {
+        Some(&fraction) => (points[index + 1], fraction),
+        None if closed => (points[0], 1.0),
+        None => return from,
+    };
+
+    let span = next_fraction - fractions[index];
+    if span > 0.0 {
+        from.lerp(to, (stop - fractions[index]) / span)
+    } else {
+        from
+    }
+}
+
+/// Simplifies a polyline with the Ramer–Douglas–Peucker algorithm. The first
+/// and the last points are always kept.
+fn douglas_peucker(points: &[Point], tolerance: f32) -> Vec<Point> {
+    if points.len() < 3 {
+        return points.to_vec();
+    }
+
+    let last = points.len() - 1;
+    let mut keep = vec![false; points.len()];
+    keep[0] = true;
+    keep[last] = true;
+
+    let mut stack = vec![(0, last)];
+    while let Some((start, end)) = stack.pop() {
+        let farthest = ((start + 1)..end)
+            .map(|i| {
+                (
+                    i,
+                    distance_to_segment(points[i], points[start], points[end]),
+                )
+            })
+            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+
+        if let Some((index, distance)) = farthest {
+            if distance > tolerance {
+                keep[index] = true;
+                stack.push((start, index));
+                stack.push((index, end));
+            }
+        }
+    }
+
+    points
+        .iter()
+        .zip(keep)
+        .filter_map(|(&point, keep)| if keep { Some(point) } else { None })
+        .collect()
+}
+
+/// Returns the distance between a point and the segment going from `from` to
+/// `to`.
+pub(crate) fn distance_to_segment(point: Point, from: Point, to: Point) -> f32 {
+    let dir = to - from;
+    let length = dir.square_length();
+    if length == 0.0 {
+        return (point - from).length();
+    }
+
+    let t = ((point - from).dot(dir) / length).clamp(0.0, 1.0);
+    (point - (from + dir * t)).length()
+}
+
+/// Moves every edge of a polygon to its right by `distance`, filling the gaps
+/// between them with circular arcs.
+///
+/// Returns `None` if the polygon has less than two distinct points, since it
+/// has no edge to move.
+#[allow(clippy::cast_sign_loss)] // `steps` is always positive
+fn offset_polygon(points: &[Point], distance: f32, tolerance: f32) -> Option<Polyline> {
+    // -- Implementation details **PLEASE KEEP UPDATED** --
+    // - `max_step`: the largest arc angle that can be approximated by a single
+    //   segment within the tolerance.
+    // - `turn`: signed angle between the incoming and the outgoing edges.
+    let radius = distance.abs();
+    // A tolerance that is too small for the precision of the radius would give
+    // an unbounded number of steps.
+    let tolerance = tolerance.max(radius * 1e-4);
+    let max_step = if tolerance < radius {
+        2.0 * (1.0 - tolerance / radius).acos()
+    } else {
+        std::f32::consts::FRAC_PI_2
+    };
+    let right = |v: Vector| Vector::new(v.y, -v.x) * distance;
+
+    // Repeated points give zero-length edges, whose direction is undefined.
+    let mut points = points.to_vec();
+    points.dedup();
+    if points.len() > 1 && points.first() == points.last() {
+        points.pop();
+    }
+    if points.len() < 2 {
+        return None;
+    }
+
+    let n = points.len();
+    let mut result = Vec::with_capacity(n * 2);
+    for (i, &corner) in points.iter().enumerate() {
+        let incoming = (corner - points[(i + n - 1) % n]).normalize();
+        let outgoing = (points[(i + 1) % n] - corner).normalize();
+        let (from, to) = (right(incoming), right(outgoing));
+        result.push(corner + from);
+
+        let turn = incoming.cross(outgoing).atan2(incoming.dot(outgoing));
+        if turn * distance > 0.0 {
+            let start = from.y.atan2(from.x);
+            let steps = (turn.abs() / max_step).ceil().max(1.0) as usize;
+            for step in 1..steps {
+                let angle = turn.mul_add(step as f32 / steps as f32, start);
+                result.push(corner + Vector::new(angle.cos(), angle.sin()) * radius);
+            }
+        }
+        result.push(corner + to);
+    }
+
+    Some(Polyline {
+        points: result,
+        closed: true,
+    })
+}
+
+/// Returns the boundary of the area described by `inside`, as closed polygons
+/// with the inside on their left. `edges` must contain every edge that may be
+/// part of the boundary.
+fn boundary(
+    edges: impl Iterator<Item = (Point, Point)>,
+    inside: impl Fn(Point) -> bool,
+    tolerance: f32,
+) -> Vec<Polyline> {
+    // Every fragment separating the inside from the outside is part of the
+    // boundary.
+    let mut fragments: Vec<(Point, Point)> = Vec::new();
+    for (from, to) in split_edges(&edges.collect::<Vec<_>>()) {
+        // The probe points must stay distinct from `mid` after rounding, so
+        // the offset can't be smaller than a few ulps of the coordinates.
+        let dir = to - from;
+        let length = dir.length();
+        let mid = from.lerp(to, 0.5);
+        let magnitude = mid.x.abs().max(mid.y.abs());
+        let distance = (length.min(tolerance) * 1e-3).max(magnitude * f32::EPSILON * 16.0);
+        let offset = Vector::new(-dir.y, dir.x) / length * distance;
+        let fragment = match (inside(mid + offset), inside(mid - offset)) {
+            (true, false) => (from, to),
+            (false, true) => (to, from),
+            _ => continue,
+        };
+
+        // Overlapping edges give duplicated fragments.
+        if !fragments.contains(&fragment) {
+            fragments.push(fragment);
+        }
+    }
+
+    link_fragments(&fragments)
+}
+
+/// Returns the edges of a set of closed polygons.
+fn edges(polygons: &[Polyline]) -> impl Iterator<Item = (Point, Point)> + '_ {
+    polygons.iter().flat_map(|polygon| {
+        let n = polygon.points.len();
+        (0..n).map(move |i| (polygon.points[i], polygon.points[(i + 1) % n]))
+    })
+}
+
+/// Splits the edges at their mutual intersections.
+///
+/// Intersection points are computed once and shared by both edges, so the
+/// endpoints of the resulting fragments can be compared exactly.
+fn split_edges(edges: &[(Point, Point)]) -> Vec<(Point, Point)> {
+    const EPSILON: f32 = 1e-6;
+    let mut splits: Vec<Vec<(f32, Point)>> = vec![Vec::new(); edges.len()];
+
+    let is_inner = |t: f32| t > EPSILON && t < 1.0 - EPSILON;
+
+    for (i, &(a0, a1)) in edges.iter().enumerate() {
+        for (j, &(b0, b1)) in edges.iter().enumerate().skip(i + 1) {
+            let (dir_a, dir_b, offset) = (a1 - a0, b1 - b0, b0 - a0);
+            let denom = dir_a.cross(dir_b);
+
+            if denom.abs() <= f32::EPSILON {
+                // Parallel edges only touch if they are collinear. In that
+                // case, each edge is split at the endpoints of the other one.
+                if offset.cross(dir_a).abs() <= EPSILON {
+                    splits[i].extend(collinear_splits((a0, a1), (b0, b1), is_inner));
+                    splits[j].extend(collinear_splits((b0, b1), (a0, a1), is_inner));
+                }
+                continue;
+            }
+
+            let ta = offset.cross(dir_b) / denom;
+            let tb = offset.cross(dir_a) / denom;
+            if ta < -EPSILON || ta > 1.0 + EPSILON || tb < -EPSILON || tb > 1.0 + EPSILON {
+                continue;
+            }
+
+            // Snap to existing vertices, to keep the fragments connected.
+            let point = if ta <= EPSILON {
+                a0
+            } else if ta >= 1.0 - EPSILON {
+                a1
+            } else if tb <= EPSILON {
+                b0
+            } else if tb >= 1.0 - EPSILON {
+                b1
+            } else {
+                a0 + dir_a * ta
+            };
+            if is_inner(ta) {
+                splits[i].push((ta, point));
+            }
+            if is_inner(tb) {
+                splits[j].push((tb, point));
+            }
+        }
+    }
+
+    fragments(edges, &mut splits)
+}
+
+/// Returns the points where an edge is split by the endpoints of a collinear
+/// edge, with their position along the first edge.
+fn collinear_splits(
+    (from, to): (Point, Point),
+    (other_from, other_to): (Point, Point),
+    is_inner: impl Fn(f32) -> bool,
+) -> Vec<(f32, Point)> {
+    let dir = to - from;
+    [other_from, other_to]
+        .iter()
+        .map(|&point| ((point - from).dot(dir) / dir.square_length(), point))
+        .filter(|&(t, _)| is_inner(t))
+        .collect()
+}
+
+/// Splits every edge at the given positions, returning the fragments.
+fn fragments(edges: &[(Point, Point)], splits: &mut [Vec<(f32, Point)>]) -> Vec<(Point, Point)> {
+    let mut fragments = Vec::new();
+    for (&(from, to), edge_splits) in edges.iter().zip(splits.iter_mut()) {
+        edge_splits.sort_by(|x, y| x.0.partial_cmp(&y.0).unwrap_or(std::cmp::Ordering::Equal));
+        let mut start = from;
+        for &end in edge_splits
+            .iter()
+            .map(|(_, p)| p)
+            .chain(std::iter::once(&to))
+        {
+            if start != end {
+                fragments.push((start, end));
+            }
+            start = end;
+        }
+    }
+
+    fragments
+}
+
+/// Links oriented fragments into closed polygons, by matching their endpoints.
+fn link_fragments(fragments: &[(Point, Point)]) -> Vec<Polyline> {
+    let key = |p: Point| (p.x.to_bits(), p.y.to_bits());
+    let mut starts: HashMap<(u32, u32), Vec<usize>> = HashMap::new();
+    for (i, &(from, _)) in fragments.iter().enumerate() {
+        starts.entry(key(from)).or_default().push(i);
+    }
+
+    let mut used = vec![false; fragments.len()];
+    let mut polygons = Vec::new();
+    for first in 0..fragments.len() {
+        let mut points = Vec::new();
+        let mut current = Some(first);
+        while let Some(i) = current.filter(|&i| !used[i]) {
+            used[i] = true;
+            points.push(fragments[i].0);
+            current = starts
+                .get(&key(fragments[i].1))
+                .and_then(|next| next.iter().copied().find(|&n| !used[n]));
+        }
+        if points.len() > 2 {
+            polygons.push(Polyline {
+                points,
+                closed: true,
+            });
+        }
+    }
+
+    polygons
+}
+
+/// Returns the signed area of a polygon, positive when it is
+/// counter-clockwise.
+fn polygon_area(points: &[Point]) -> f32 {
+    let next = points.iter().cycle().skip(1);
+    points
+        .iter()
+        .zip(next)
+        .map(|(a, b)| a.to_vector().cross(b.to_vector()))
+        .sum::<f32>()
+        / 2.0
+}
+
+/// Splits a path into the events of each of its sub-paths.
+pub(crate) fn subpaths(path: &Path) -> Vec<Vec<PathEvent>> {
+    let mut subpaths = Vec::new();
+    let mut current = Vec::new();
+    for event in path.iter() {
+        let end = matches!(event, PathEvent::End { .. });
+        current.push(event);
+        if end {
+            subpaths.push(std::mem::take(&mut current));
+        }
+    }
+
+    subpaths
+}
+
+/// Adds the events of a sub-path to a builder.
+pub(crate) fn add_events(builder: &mut Builder, events: &[PathEvent]) {
+    for event in events {
+        match *event {
+            PathEvent::Begin { at } => {
+                builder.begin(at);
+            }
+            PathEvent::Line { to, .. } => {
+                builder.line_to(to);
+            }
+            PathEvent::Quadratic { ctrl, to, .. } => {
+                builder.quadratic_bezier_to(ctrl, to);
+            }
+            PathEvent::Cubic {
+                ctrl1, ctrl2, to, ..
+            } => {
+                builder.cubic_bezier_to(ctrl1, ctrl2, to);
+            }
+            PathEvent::End { close, .. } => builder.end(close),
+        }
+    }
+}
+
+/// Adds the events of a sub-path to a builder, in the opposite direction.
+/// Closed sub-paths start from their last point, so the implicit closing
+/// segment stays the same.
+fn add_reversed(builder: &mut Builder, events: &[PathEvent]) {
+    let (last, close) = match events.last() {
+        Some(&PathEvent::End { last, close, .. }) => (last, close),
+        _ => return,
+    };
+
+    builder.begin(last);
+    for event in events.iter().rev() {
+        match *event {
+            PathEvent::Line { from, .. } => {
+                builder.line_to(from);
+            }
+            PathEvent::Quadratic { from, ctrl, .. } => {
+                builder.quadratic_bezier_to(ctrl, from);
+            }
+            PathEvent::Cubic {
+                from, ctrl1, ctrl2, ..
+            } => {
+                builder.cubic_bezier_to(ctrl2, ctrl1, from);
+            }
+            PathEvent::Begin { .. } | PathEvent::End { .. } => {}
+        }
+    }
+    builder.end(close);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::PI;
+
+    use super::*;
+    use crate::shapes::{Line, Polygon};
+
+    fn square(x: f32, y: f32, size: f32) -> Polygon {
+        Polygon {
+            points: vec![
+                Vec2::new(x, y),
+                Vec2::new(x + size, y),
+                Vec2::new(x + size, y + size),
+                Vec2::new(x, y + size),
+            ],
+            closed: true,
+            ..Polygon::default()
+        }
+    }
+
+    fn area(path: &Path) -> f32 {
+        signed_areas(path, 0.1).iter().sum()
+    }
+
+    fn assert_area(path: &Path, expected: f32) {
+        let actual = area(path);
+        assert!(
+            (actual - expected).abs() < expected * 1e-3,
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn boolean_ops_far_from_the_origin() {
+        let a = square(5000.0, 5000.0, 100.0);
+        let b = square(5050.0, 5050.0, 100.0);
+
+        assert_area(&union(&a, &b, 0.1), 17500.0);
+        assert_area(&intersection(&a, &b, 0.1), 2500.0);
+        assert_area(&difference(&a, &b, 0.1), 7500.0);
+        assert_area(&xor(&a, &b, 0.1), 15000.0);
+    }
+
+    #[test]
+    fn offset_far_from_the_origin() {
+        let grown = offset(&square(8000.0, -8000.0, 100.0), 10.0, 0.01);
+
+        assert_area(&grown, PI.mul_add(100.0, 14000.0));
+    }
+
+    #[test]
+    fn offset_with_zero_tolerance() {
+        let grown = offset(&square(0.0, 0.0, 100.0), 10.0, 0.0);
+
+        assert_area(&grown, PI.mul_add(100.0, 14000.0));
+    }
+
+    #[test]
+    fn dash_splits_the_outline() {
+        let line = Line(Vec2::ZERO, Vec2::new(128.0, 0.0));
+
+        let dashes = flatten(&dash(&line, &[16.0, 8.0], 0.0, 0.1), 0.1);
+
+        assert_eq!(dashes.len(), 6);
+        assert_eq!(
+            dashes[1].points,
+            vec![Point::new(24.0, 0.0), Point::new(40.0, 0.0)]
+        );
+        assert_eq!(
+            dashes[5].points,
+            vec![Point::new(120.0, 0.0), Point::new(128.0, 0.0)]
+        );
+    }
+
+    #[test]
+    fn dash_keeps_the_outline_when_the_pattern_is_too_short() {
+        let line = Line(Vec2::ZERO, Vec2::new(1.0e6, 0.0));
+
+        let dashes = flatten(&dash(&line, &[0.001, 0.001], 0.0, 0.1), 0.1);
+
+        assert_eq!(dashes.len(), 1);
+    }
+
+    #[test]
+    fn split_at_fractions_adds_points_at_the_stops() {
+        let line = to_path(&Line(Vec2::ZERO, Vec2::new(10.0, 0.0)));
+
+        let split = flatten(&split_at_fractions(&line, &[0.0, 0.25, 0.5, 1.0], 0.1), 0.1);
+
+        let xs: Vec<f32> = split[0].points.iter().map(|point| point.x).collect();
+        assert_eq!(xs, vec![0.0, 2.5, 5.0, 10.0]);
+    }
+}
@@ -4,111 +4,1337 @@
 //! boilerplate.
 //!
 //! ## How it works
-//! The user spawns a [`ShapeBundle`](crate::entity::ShapeBundle) from a
-//! system in the `UPDATE` stage.
+//! The user spawns a [`ShapeBundle`](crate::entity::ShapeBundle) from any
+//! system, in any stage up to `POST_UPDATE`, or from a startup system.
 //!
-//! Then, in [`Stage::Shape`] stage, there is a system
+//! Then, in [`Stage::Shape`] stage, which runs after `POST_UPDATE`, so shapes
+//! are drawn in the frame they are spawned, there is a system
 //! that creates a mesh for each entity that has been spawned as a
-//! `ShapeBundle`.
+//! `ShapeBundle`, or whose [`Path`], [`DrawMode`] or
+//! [optional components](#optional-components) have changed. Zero-length
+//! sub-paths are dropped with a warning, and paths with other problems found
+//! by [`validate`] are not tessellated, the error being logged instead.
+//! The same stage creates the quad of each
+//! [`SdfShapeBundle`](crate::sdf::SdfShapeBundle).
+//!
+//! When the [`ShapeColors`] of a shape change, another system of the stage
+//! writes the new colors into the vertices of its mesh, without tessellating
+//! it again.
+//!
+//! The mesh of a shape is referenced only by the strong `Handle<Mesh>` of its
+//! entity. When the entity is despawned, or when its mesh is replaced after a
+//! change of its path or draw mode, the handle is dropped and Bevy removes the
+//! mesh from `Assets<Mesh>`, so no cleanup system is needed.
+//!
+//! ## Optional components
+//! The mesh of a shape can be customized by inserting some components of this
+//! module on its entity: [`Gradient`], [`UvMapping`], [`VertexModifier`],
+//! [`WidthProfile`], [`StrokeDash`], [`Feathering`], [`DropShadow`],
+//! [`PixelSnap`], [`MergedShapes`] and [`MeshAttributes`]. They can be inserted
+//! along with the [`ShapeBundle`](crate::entity::ShapeBundle), or at any later
+//! time: inserting, changing or removing one of them gives the shape a new
+//! mesh.
+//!
+//! Shapes without any of the optional components of this module share their
+//! mesh with the other shapes having the same path, draw mode and colors, so
+//! identical shapes are only tessellated once. Changing the colors of such a
+//! shape gives it its own copy of the mesh.
+
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+    iter,
+    marker::PhantomData,
+};
 
 use bevy::{
-    app::{AppBuilder, Plugin},
-    asset::{Assets, Handle},
+    app::{AppBuilder, CoreStage, Plugin},
+    asset::{AssetEvent, Assets, Handle},
     ecs::{
-        query::Added,
-        schedule::{StageLabel, SystemStage},
-        system::{IntoSystem, Query, ResMut},
+        component::Component,
+        entity::Entity,
+        event::EventReader,
+        query::{Added, ChangeTrackers, Changed, Or, Without},
+        schedule::{
+            ParallelSystemDescriptorCoercion, ShouldRun, StageLabel, SystemLabel, SystemSet,
+            SystemStage,
+        },
+        system::{Commands, IntoSystem, Query, RemovedComponents, Res, ResMut},
     },
-    log::error,
+    log::{error, warn},
+    math::{Vec2, Vec3},
     render::{
+        camera::{Camera, OrthographicProjection},
         color::Color,
         draw::Visible,
-        mesh::{Indices, Mesh},
+        mesh::{Indices, Mesh, VertexAttributeValues},
         pipeline::PrimitiveTopology,
+        render_graph::base::camera::CAMERA_2D,
+    },
+    transform::{
+        components::{Children, GlobalTransform, Parent, Transform},
+        TransformSystem,
     },
 };
 use lyon_tessellation::{
-    self as tess, path::Path, BuffersBuilder, FillTessellator, FillVertex, FillVertexConstructor,
-    StrokeTessellator, StrokeVertex, StrokeVertexConstructor,
+    self as tess,
+    math::{Point, Vector},
+    path::{IdEvent, Path, PathEvent},
+    BuffersBuilder, FillTessellator, FillVertex, FillVertexConstructor, StrokeTessellator,
+    StrokeVertex, StrokeVertexConstructor, VertexSource,
 };
 use tess::{FillOptions, StrokeOptions};
 
-use crate::{entity::ShapeColors, utils::DrawMode};
+use crate::{
+    entity::{GlobalShapeAlpha, ShapeAlpha, ShapeColors},
+    geometry::Geometry,
+    gradient::{interpolate_sorted, Gradient},
+    path::{remove_zero_length, validate, PathError},
+    path_ops::{self, flatten, to_path},
+    render::{apply_blend_modes, update_wireframes},
+    sdf::{complete_sdf_bundle, SdfPrimitive},
+    utils::{Convert, DefaultDrawMode, DrawMode, ShapeSettings},
+};
 
 /// Stages for this plugin.
 #[derive(Debug, Clone, Eq, Hash, PartialEq, StageLabel)]
 pub enum Stage {
     /// The stage where the [`ShapeBundle`](crate::entity::ShapeBundle) gets
-    /// completed.
+    /// completed. It runs after `POST_UPDATE`, so it also completes the shapes
+    /// spawned in that stage.
     Shape,
 }
 
+/// Labels of the systems of this plugin.
+#[derive(Debug, Clone, Eq, Hash, PartialEq, SystemLabel)]
+pub enum ShapeSystem {
+    /// The system creating the mesh of every
+    /// [`ShapeBundle`](crate::entity::ShapeBundle).
+    Tessellation,
+}
+
 /// The index type of a Bevy [`Mesh`](bevy::render::mesh::Mesh).
-type IndexType = u32;
+pub type IndexType = u32;
 /// Lyon's [`VertexBuffers`] generic data type defined for [`Vertex`].
-type VertexBuffers = tess::VertexBuffers<Vertex, IndexType>;
+///
+/// Fill it with a lyon tessellator and a [`VertexConstructor`], then turn it
+/// into a mesh with [`build_mesh`].
+pub type VertexBuffers = tess::VertexBuffers<Vertex, IndexType>;
+
+/// The name of the mesh attribute holding the positions of the vertices, which
+/// are two-dimensional.
+pub const ATTRIBUTE_POSITION_2D: &str = "Vertex_Position_2D";
+
+/// The optional components changing how the vertices of a shape are built.
+type VertexOptions<'a> = (
+    Option<&'a Gradient>,
+    Option<&'a VertexModifier>,
+    Option<&'a UvMapping>,
+    Option<&'a WidthProfile>,
+);
+
+/// The optional components changing how the mesh of a shape is built.
+type MeshOptions<'a> = (
+    Option<&'a StrokeDash>,
+    Option<&'a Feathering>,
+    Option<&'a DropShadow>,
+    Option<&'a PixelSnap>,
+    Option<&'a MergedShapes>,
+    Option<&'a MeshAttributes>,
+);
+
+/// The components whose insertion or change gives a shape a new mesh.
+type ShapeChanged = Or<(
+    Changed<Path>,
+    Changed<DrawMode>,
+    Changed<Gradient>,
+    Changed<VertexModifier>,
+    Changed<UvMapping>,
+    Changed<WidthProfile>,
+    Changed<StrokeDash>,
+    Changed<Feathering>,
+    Changed<DropShadow>,
+    Changed<PixelSnap>,
+    Changed<MergedShapes>,
+    Changed<MeshAttributes>,
+)>;
+
+/// The optional components whose removal gives a shape a new mesh.
+type RemovedOptions<'a> = (
+    RemovedComponents<'a, Gradient>,
+    RemovedComponents<'a, VertexModifier>,
+    RemovedComponents<'a, UvMapping>,
+    RemovedComponents<'a, WidthProfile>,
+    RemovedComponents<'a, StrokeDash>,
+    RemovedComponents<'a, Feathering>,
+    RemovedComponents<'a, DropShadow>,
+    RemovedComponents<'a, PixelSnap>,
+    RemovedComponents<'a, MergedShapes>,
+    RemovedComponents<'a, MeshAttributes>,
+);
+
+/// Returns the entities that lost one of the optional components this frame.
+fn removed_options<'a>(removed: &'a RemovedOptions<'_>) -> impl Iterator<Item = Entity> + 'a {
+    let (
+        gradients,
+        modifiers,
+        uvs,
+        widths,
+        dashes,
+        featherings,
+        shadows,
+        snaps,
+        merged,
+        attributes,
+    ) = removed;
+    gradients
+        .iter()
+        .chain(modifiers.iter())
+        .chain(uvs.iter())
+        .chain(widths.iter())
+        .chain(dashes.iter())
+        .chain(featherings.iter())
+        .chain(shadows.iter())
+        .chain(snaps.iter())
+        .chain(merged.iter())
+        .chain(attributes.iter())
+}
 
 /// A vertex with all the necessary attributes to be inserted into a Bevy
 /// [`Mesh`](bevy::render::mesh::Mesh).
 #[derive(Debug, Clone, Copy, PartialEq)]
-struct Vertex {
-    position: [f32; 2],
-    color: [f32; 4],
+pub struct Vertex {
+    /// The position of the vertex, in the local coordinates of the shape.
+    pub position: [f32; 2],
+    /// The color of the vertex, as RGBA components.
+    pub color: [f32; 4],
+    /// The texture coordinates of the vertex, generated according to the
+    /// [`UvMapping`] of the shape.
+    pub uv: [f32; 2],
+}
+
+/// An [optional component](crate::plugin#optional-components) determining how
+/// the texture coordinates of the vertices of a shape are generated. Shapes
+/// without it use [`UvMapping::BoundingBox`].
+///
+/// The tiled mappings need a texture whose sampler repeats, by setting its
+/// address modes to `AddressMode::Repeat`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UvMapping {
+    /// The texture is stretched over the bounding box of the path.
+    BoundingBox,
+    /// The texture is repeated every `size` units, in the local coordinates of
+    /// the shape, starting from `offset`.
+    #[allow(missing_docs)]
+    Tiled { size: Vec2, offset: Vec2 },
+    /// Like [`UvMapping::Tiled`], but in world coordinates, so the tiling
+    /// continues across adjacent shapes. The [`Transform`] of the entity is
+    /// used when the mesh is created, so the texture moves with the shape
+    /// afterwards.
+    #[allow(missing_docs)]
+    World { size: Vec2, offset: Vec2 },
+}
+
+impl Default for UvMapping {
+    fn default() -> Self {
+        Self::BoundingBox
+    }
+}
+
+/// The texture coordinates frame of a shape: positions are transformed by
+/// `transform` if there is one, then `origin` is mapped to the top-left corner
+/// of the texture, and `origin + size` to the bottom-right corner.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct UvFrame {
+    transform: Option<Transform>,
+    origin: Vec2,
+    size: Vec2,
+}
+
+impl UvFrame {
+    fn new(mapping: UvMapping, path: &Path, transform: &Transform) -> Self {
+        match mapping {
+            UvMapping::BoundingBox => {
+                let (min, size) = bounds(path);
+                Self {
+                    transform: None,
+                    origin: min.convert(),
+                    size: Vec2::new(size.x, size.y),
+                }
+            }
+            UvMapping::Tiled { size, offset } => Self {
+                transform: None,
+                origin: offset,
+                size,
+            },
+            UvMapping::World { size, offset } => Self {
+                transform: Some(*transform),
+                origin: offset,
+                size,
+            },
+        }
+    }
+
+    fn uv(&self, position: Point) -> [f32; 2] {
+        let position = self.transform.map_or(position.convert(), |transform| {
+            let world = transform.mul_vec3(Vec3::new(position.x, position.y, 0.0));
+            Vec2::new(world.x, world.y)
+        });
+        let coordinate = |value: f32, origin: f32, size: f32| {
+            if size.abs() > 0.0 {
+                (value - origin) / size
+            } else {
+                0.0
+            }
+        };
+
+        [
+            coordinate(position.x, self.origin.x, self.size.x),
+            1.0 - coordinate(position.y, self.origin.y, self.size.y),
+        ]
+    }
+}
+
+/// Antialiases the edges of a shape without multisampling, by extruding a
+/// rim of the given width around it, whose color fades to transparent.
+///
+/// Like the other [optional components](crate::plugin#optional-components), it
+/// is applied when the mesh is created. The width is in the local coordinates
+/// of the shape, so it should be about one pixel at the scale the shape is
+/// drawn. With [`DrawMode::Outlined`], only the outline is feathered.
+///
+/// # Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_prototype_lyon::{plugin::Feathering, prelude::*};
+///
+/// fn some_system(mut commands: Commands) {
+///     let circle = shapes::Circle {
+///         radius: 100.0,
+///         ..shapes::Circle::default()
+///     };
+///     commands
+///         .spawn_bundle(GeometryBuilder::build_as(
+///             &circle,
+///             ShapeColors::new(Color::CRIMSON),
+///             DrawMode::Fill(FillOptions::default()),
+///             Transform::default(),
+///         ))
+///         .insert(Feathering(1.0));
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Feathering(pub f32);
+
+impl Default for Feathering {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+/// Dashes the stroke of a shape, or its outline with [`DrawMode::Outlined`],
+/// without changing its path. It is one of the
+/// [optional components](crate::plugin#optional-components) of a shape.
+///
+/// The path is split with [`path_ops::dash`](crate::path_ops::dash), using the
+/// tolerance of the [`StrokeOptions`], and every dash gets its own caps, while
+/// the joins inside a dash are kept. Like there, the outline stays undashed if
+/// the pattern is too short for its length.
+///
+/// # Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_prototype_lyon::{plugin::StrokeDash, prelude::*};
+///
+/// fn some_system(mut commands: Commands) {
+///     let square = shapes::Rectangle {
+///         width: 100.0,
+///         height: 100.0,
+///         ..shapes::Rectangle::default()
+///     };
+///     commands
+///         .spawn_bundle(GeometryBuilder::build_as(
+///             &square,
+///             ShapeColors::new(Color::BLACK),
+///             DrawMode::Stroke(StrokeOptions::default().with_line_width(2.0)),
+///             Transform::default(),
+///         ))
+///         // 10 units long dashes, separated by 5 units long gaps.
+///         .insert(StrokeDash {
+///             pattern: vec![10.0, 5.0],
+///             offset: 0.0,
+///         });
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct StrokeDash {
+    /// The lengths of the dashes and of the gaps between them, starting with
+    /// a dash. If it has an odd number of elements, it is repeated.
+    pub pattern: Vec<f32>,
+    /// The distance the pattern is shifted backwards along the outline.
+    pub offset: f32,
+}
+
+/// A function customizing the vertices of a shape before they are stored in
+/// its mesh, allowing per-vertex data like procedural colors.
+///
+/// As an [optional component](crate::plugin#optional-components) of a shape,
+/// the function is called for every vertex of the fill and of the outline,
+/// after its color has been set from the [`ShapeColors`] or from the
+/// [`Gradient`]. To give the vertices attributes of their own, for a custom
+/// pipeline, use [`MeshAttributes`].
+///
+/// # Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_prototype_lyon::{plugin::VertexModifier, prelude::*};
+///
+/// fn some_system(mut commands: Commands) {
+///     let circle = shapes::Circle {
+///         radius: 100.0,
+///         ..shapes::Circle::default()
+///     };
+///     commands
+///         .spawn_bundle(GeometryBuilder::build_as(
+///             &circle,
+///             ShapeColors::new(Color::WHITE),
+///             DrawMode::Fill(FillOptions::default()),
+///             Transform::default(),
+///         ))
+///         // Fades the shape from the top to the bottom.
+///         .insert(VertexModifier::new(|vertex| {
+///             vertex.color[3] = (vertex.position[1] / 200.0 + 0.5).clamp(0.0, 1.0);
+///         }));
+/// }
+/// ```
+pub struct VertexModifier(Box<dyn Fn(&mut Vertex) + Send + Sync>);
+
+impl VertexModifier {
+    /// Creates a `VertexModifier` from a function.
+    #[must_use]
+    pub fn new(modifier: impl Fn(&mut Vertex) + Send + Sync + 'static) -> Self {
+        Self(Box::new(modifier))
+    }
+}
+
+/// A function adding vertex attributes to the mesh of a shape, for a custom
+/// pipeline drawing it with a
+/// [`MaterialShapeBundle`](crate::entity::MaterialShapeBundle).
+///
+/// As an [optional component](crate::plugin#optional-components), the
+/// function is called every time the mesh is created, with the tessellated
+/// vertices, including the ones added by the other optional components. The
+/// attributes must have one value for each vertex, in the same order.
+///
+/// # Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_prototype_lyon::{plugin::MeshAttributes, prelude::*};
+///
+/// fn some_system(mut commands: Commands) {
+///     let circle = shapes::Circle {
+///         radius: 100.0,
+///         ..shapes::Circle::default()
+///     };
+///     commands
+///         .spawn_bundle(GeometryBuilder::build_as(
+///             &circle,
+///             ShapeColors::new(Color::WHITE),
+///             DrawMode::Fill(FillOptions::default()),
+///             Transform::default(),
+///         ))
+///         // The distance of every vertex from the center, for a custom shader.
+///         .insert(MeshAttributes::new(|buffers, mesh| {
+///             let distances: Vec<f32> = buffers
+///                 .vertices
+///                 .iter()
+///                 .map(|vertex| Vec2::from(vertex.position).length())
+///                 .collect();
+///             mesh.set_attribute("Vertex_Distance", distances);
+///         }));
+/// }
+/// ```
+pub struct MeshAttributes(Box<dyn Fn(&VertexBuffers, &mut Mesh) + Send + Sync>);
+
+impl MeshAttributes {
+    /// Creates a `MeshAttributes` from a function.
+    #[must_use]
+    pub fn new(attributes: impl Fn(&VertexBuffers, &mut Mesh) + Send + Sync + 'static) -> Self {
+        Self(Box::new(attributes))
+    }
+}
+
+/// The width of a stroke along its length, allowing strokes to taper like
+/// brush strokes.
+///
+/// This [optional component](crate::plugin#optional-components) is applied to
+/// the stroke of [`DrawMode::Stroke`], and to the outline of
+/// [`DrawMode::Outlined`].
+///
+/// The function receives the fraction of the length of the sub-path at which
+/// a vertex lies, from `0.0` to `1.0`, and returns the width of the stroke
+/// there. The stroke is tessellated with the width of its [`StrokeOptions`],
+/// then every vertex is moved towards or away from the path, so the line
+/// width of the options must be positive. Since the width changes linearly
+/// between the vertices, lowering the tolerance gives a smoother result on
+/// curves.
+///
+/// # Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_prototype_lyon::{plugin::WidthProfile, prelude::*};
+///
+/// fn some_system(mut commands: Commands) {
+///     let wave = shapes::QuadraticBezier {
+///         from: Vec2::new(-100.0, 0.0),
+///         ctrl: Vec2::new(0.0, 100.0),
+///         to: Vec2::new(100.0, 0.0),
+///     };
+///     commands
+///         .spawn_bundle(GeometryBuilder::build_as(
+///             &wave,
+///             ShapeColors::new(Color::BLACK),
+///             DrawMode::Stroke(StrokeOptions::default().with_line_width(10.0)),
+///             Transform::default(),
+///         ))
+///         // Thin at both ends, 10 units wide in the middle.
+///         .insert(WidthProfile::from_points(vec![
+///             (0.0, 1.0),
+///             (0.5, 10.0),
+///             (1.0, 1.0),
+///         ]));
+/// }
+/// ```
+pub struct WidthProfile(Box<dyn Fn(f32) -> f32 + Send + Sync>);
+
+impl WidthProfile {
+    /// Creates a `WidthProfile` from a function.
+    #[must_use]
+    pub fn new(profile: impl Fn(f32) -> f32 + Send + Sync + 'static) -> Self {
+        Self(Box::new(profile))
+    }
+
+    /// Creates a `WidthProfile` interpolating linearly between the given
+    /// `(fraction, width)` pairs, which are expected to be sorted by fraction.
+    /// Before the first pair and after the last one, the width is constant.
+    /// Without pairs, the width is zero.
+    #[must_use]
+    pub fn from_points(points: Vec<(f32, f32)>) -> Self {
+        Self::new(move |fraction| {
+            interpolate_sorted(
+                &points,
+                fraction,
+                |&point| point,
+                |from, to, t| (to - from).mul_add(t, from),
+            )
+            .unwrap_or(0.0)
+        })
+    }
+}
+
+/// Keeps the stroke of a shape, or its outline with [`DrawMode::Outlined`],
+/// the given number of pixels wide, regardless of the zoom of the 2D camera
+/// and of the scale of the shape.
+///
+/// Insert it as a component on an entity spawned with a
+/// [`ShapeBundle`](crate::entity::ShapeBundle). Every frame, the line width
+/// in its [`DrawMode`] is computed from the scale of the
+/// [`OrthographicProjection`] and of the [`GlobalTransform`] of the camera, and
+/// from the [`GlobalTransform`] of the shape, at the end of `POST_UPDATE`. When
+/// it changes, the shape is tessellated again in the same frame, so zooming
+/// continuously re-tessellates the shape every frame.
+///
+/// # Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_prototype_lyon::{plugin::ScreenSpaceStroke, prelude::*};
+///
+/// fn some_system(mut commands: Commands) {
+///     let road = shapes::Line(Vec2::ZERO, Vec2::new(1000.0, 500.0));
+///     commands
+///         .spawn_bundle(GeometryBuilder::build_as(
+///             &road,
+///             ShapeColors::new(Color::GRAY),
+///             DrawMode::Stroke(StrokeOptions::default()),
+///             Transform::default(),
+///         ))
+///         .insert(ScreenSpaceStroke(2.0));
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScreenSpaceStroke(pub f32);
+
+/// Draws a shadow behind a shape: a copy of the shape, moved by `offset` and
+/// filled with `color`, whose edges fade to transparent over `blur` units.
+/// With a zero offset and a light color, it makes a glow.
+///
+/// As an [optional component](crate::plugin#optional-components), the shadow
+/// is part of the mesh of the shape, before it, so it is always drawn behind
+/// the shape. It follows the
+/// silhouette of the fill and of the stroke, but ignores the
+/// [`StrokeDash`] and the [`WidthProfile`] of the shape.
+///
+/// # Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_prototype_lyon::{plugin::DropShadow, prelude::*};
+///
+/// fn some_system(mut commands: Commands) {
+///     let panel = shapes::Rectangle {
+///         width: 200.0,
+///         height: 100.0,
+///         ..shapes::Rectangle::default()
+///     };
+///     commands
+///         .spawn_bundle(GeometryBuilder::build_as(
+///             &panel,
+///             ShapeColors::new(Color::ANTIQUE_WHITE),
+///             DrawMode::Fill(FillOptions::default()),
+///             Transform::default(),
+///         ))
+///         .insert(DropShadow {
+///             offset: Vec2::new(5.0, -5.0),
+///             blur: 8.0,
+///             color: Color::rgba(0.0, 0.0, 0.0, 0.4),
+///         });
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DropShadow {
+    /// The position of the shadow relative to the shape, in its local
+    /// coordinates.
+    pub offset: Vec2,
+    /// The width of the faded edge of the shadow.
+    pub blur: f32,
+    /// The color of the shadow.
+    pub color: Color,
+}
+
+impl Default for DropShadow {
+    fn default() -> Self {
+        Self {
+            offset: Vec2::new(4.0, -4.0),
+            blur: 4.0,
+            color: Color::rgba(0.0, 0.0, 0.0, 0.5),
+        }
+    }
+}
+
+/// The draw order of a shape. Shapes with a greater index are drawn on top of
+/// the ones with a lower index.
+///
+/// Insert it as a component on an entity spawned with a
+/// [`ShapeBundle`](crate::entity::ShapeBundle). Whenever it changes, it is
+/// copied into the z coordinate of the translation of its [`Transform`], which
+/// determines the draw order of transparent meshes, overriding the spawn
+/// order. The index must be within the depth range of the camera, which is
+/// `-1000.0..1000.0` for the default 2D camera.
+///
+/// # Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_prototype_lyon::{plugin::ZIndex, prelude::*};
+///
+/// fn some_system(mut commands: Commands) {
+///     let square = shapes::Rectangle {
+///         width: 100.0,
+///         height: 100.0,
+///         ..shapes::Rectangle::default()
+///     };
+///     // The red square is drawn on top of the blue one, although it is
+///     // spawned first.
+///     for (color, index) in &[(Color::RED, 2.0), (Color::BLUE, 1.0)] {
+///         commands
+///             .spawn_bundle(GeometryBuilder::build_as(
+///                 &square,
+///                 ShapeColors::new(*color),
+///                 DrawMode::Fill(FillOptions::default()),
+///                 Transform::default(),
+///             ))
+///             .insert(ZIndex(*index));
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ZIndex(pub f32);
+
+/// Snaps a shape to a grid of pixels, for crisp edges and one pixel wide
+/// strokes in pixel art and user interfaces.
+///
+/// As an [optional component](crate::plugin#optional-components), it rounds the
+/// vertices of the mesh to multiples of the given pixel size, in the local
+/// coordinates of the shape, and the translation of its
+/// [`Transform`] is kept rounded to multiples of the pixel size as well. The
+/// shape is only aligned to the pixels of the screen if its parents and the
+/// camera are aligned too, and if it is neither rotated nor scaled.
+///
+/// To stroke lines exactly one pixel wide, use a line width of one pixel on a
+/// path whose points are at the center of pixels, like `0.5`.
+///
+/// # Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_prototype_lyon::{plugin::PixelSnap, prelude::*};
+///
+/// fn some_system(mut commands: Commands) {
+///     let frame = shapes::Rectangle {
+///         width: 64.0,
+///         height: 32.0,
+///         origin: shapes::RectangleOrigin::CustomCenter(Vec2::new(0.5, 0.5)),
+///     };
+///     commands
+///         .spawn_bundle(GeometryBuilder::build_as(
+///             &frame,
+///             ShapeColors::new(Color::WHITE),
+///             DrawMode::Stroke(StrokeOptions::default().with_line_width(1.0)),
+///             Transform::default(),
+///         ))
+///         .insert(PixelSnap(1.0));
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PixelSnap(pub f32);
+
+impl PixelSnap {
+    /// Rounds a coordinate to the nearest multiple of the pixel size. Halves
+    /// are always rounded up, so the edges of a one pixel wide stroke are
+    /// never merged nor pulled apart.
+    fn snap(self, value: f32) -> f32 {
+        if self.0 > 0.0 {
+            (value / self.0 + 0.5).floor() * self.0
+        } else {
+            value
+        }
+    }
+
+    fn snap_vertices(self, buffers: &mut VertexBuffers) {
+        for vertex in &mut buffers.vertices {
+            vertex.position = [self.snap(vertex.position[0]), self.snap(vertex.position[1])];
+        }
+    }
+}
+
+impl Default for PixelSnap {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+/// A shape drawn by a [`MergedShapes`] component, with its own draw mode and
+/// colors.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergedShape {
+    /// The path of the shape, in the local coordinates of the entity.
+    pub path: Path,
+    /// How the path is tessellated.
+    pub mode: DrawMode,
+    /// The colors of the shape.
+    pub colors: ShapeColors,
+}
+
+/// Several shapes drawn with the single mesh of one entity, each with its own
+/// [`DrawMode`] and [`ShapeColors`].
+///
+/// When this component is present, the mesh of the shape is made of these
+/// shapes, and its own [`Path`], [`DrawMode`] and [`ShapeColors`] are not
+/// drawn. The other optional components of this module, except
+/// [`MeshAttributes`], are not applied either. Use
+/// [`GeometryBuilder::build_merged`](crate::geometry::GeometryBuilder::build_merged)
+/// to create it.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MergedShapes(pub Vec<MergedShape>);
+
+/// Where a vertex of a shape mesh takes its color from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ColorSource {
+    /// The main color of the [`ShapeColors`], with its alpha multiplied by
+    /// the given factor.
+    Main(f32),
+    /// The outline color of the [`ShapeColors`], with its alpha multiplied by
+    /// the given factor.
+    Outline(f32),
+    /// A color that does not depend on the [`ShapeColors`], like the ones of
+    /// gradients and shadows, or one changed by a [`VertexModifier`].
+    Fixed,
+}
+
+impl ColorSource {
+    /// Returns the same source with a transparent color, like the one of the
+    /// rim vertices of a [`Feathering`].
+    const fn transparent(self) -> Self {
+        match self {
+            Self::Main(_) => Self::Main(0.0),
+            Self::Outline(_) => Self::Outline(0.0),
+            Self::Fixed => Self::Fixed,
+        }
+    }
+
+    fn color(self, colors: &ShapeColors, current: [f32; 4]) -> [f32; 4] {
+        let (color, alpha) = match self {
+            Self::Main(alpha) => (colors.main, alpha),
+            Self::Outline(alpha) => (colors.outline, alpha),
+            Self::Fixed => return current,
+        };
+        [color.r(), color.g(), color.b(), color.a() * alpha]
+    }
+}
+
+/// The [`ColorSource`] of every vertex of the mesh of a shape, so its colors
+/// can be changed without tessellating it again.
+struct VertexColorSources(Vec<ColorSource>);
+
+/// Marks the shapes whose mesh comes from the [`MeshCache`], and may be used
+/// by other shapes.
+struct SharedMesh;
+
+/// The meshes of the shapes without optional components, with the
+/// [`ColorSource`]s of their vertices, keyed by their path, draw mode and
+/// colors.
+///
+/// The handles are weak, so the meshes are freed once no shape uses them.
+#[derive(Default)]
+struct MeshCache(HashMap<MeshKey, (Handle<Mesh>, Vec<ColorSource>)>);
+
+/// The data a cached mesh is built from, as the bits of its numbers. The whole
+/// data is compared when the hashes of two keys are equal, so different shapes
+/// never share a mesh.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct MeshKey {
+    path: Vec<u32>,
+    mode: Vec<u32>,
+    colors: [u32; 8],
 }
 
-/// Zero-sized type used to implement various vertex construction traits from
-/// Lyon.
-struct VertexConstructor {
+impl MeshCache {
+    /// Returns the key of a shape, or `None` if it has optional components,
+    /// which are not part of the key.
+    fn key(
+        path: &Path,
+        mode: &DrawMode,
+        colors: &ShapeColors,
+        vertex_options: VertexOptions<'_>,
+        mesh_options: MeshOptions<'_>,
+    ) -> Option<MeshKey> {
+        if !matches!(
+            (vertex_options, mesh_options),
+            (
+                (None, None, None, None),
+                (None, None, None, None, None, None)
+            )
+        ) {
+            return None;
+        }
+
+        let (main, outline) = (colors.main, colors.outline);
+        Some(MeshKey {
+            path: path_bits(path),
+            mode: draw_mode_bits(mode),
+            colors: [
+                main.r().to_bits(),
+                main.g().to_bits(),
+                main.b().to_bits(),
+                main.a().to_bits(),
+                outline.r().to_bits(),
+                outline.g().to_bits(),
+                outline.b().to_bits(),
+                outline.a().to_bits(),
+            ],
+        })
+    }
+
+    /// Returns a strong handle to the cached mesh with the given key, if it
+    /// has not been freed, with the sources of its vertex colors.
+    fn get(
+        &self,
+        meshes: &Assets<Mesh>,
+        key: &MeshKey,
+    ) -> Option<(Handle<Mesh>, Vec<ColorSource>)> {
+        let (handle, sources) = self.0.get(key)?;
+        meshes.get(handle)?;
+        Some((meshes.get_handle(handle), sources.clone()))
+    }
+}
+
+/// Returns the kind and the points of every event of a path, as integers.
+fn path_bits(path: &Path) -> Vec<u32> {
+    let mut bits = Vec::new();
+    let mut push = |verb: u32, points: &[Point]| {
+        bits.push(verb);
+        for point in points {
+            bits.push(point.x.to_bits());
+            bits.push(point.y.to_bits());
+        }
+    };
+    for event in path.iter() {
+        match event {
+            PathEvent::Begin { at } => push(0, &[at]),
+            PathEvent::Line { to, .. } => push(1, &[to]),
+            PathEvent::Quadratic { ctrl, to, .. } => push(2, &[ctrl, to]),
+            PathEvent::Cubic {
+                ctrl1, ctrl2, to, ..
+            } => push(3, &[ctrl1, ctrl2, to]),
+            PathEvent::End { close, .. } => push(4 + u32::from(close), &[]),
+        }
+    }
+
+    bits
+}
+
+/// Returns the variant and the options of a draw mode, as integers.
+fn draw_mode_bits(mode: &DrawMode) -> Vec<u32> {
+    let fill = |options: &FillOptions| {
+        vec![
+            options.tolerance.to_bits(),
+            options.fill_rule as u32,
+            options.sweep_orientation as u32,
+            u32::from(options.handle_intersections),
+        ]
+    };
+    let stroke = |options: &StrokeOptions| {
+        vec![
+            options.tolerance.to_bits(),
+            options.line_width.to_bits(),
+            options.miter_limit.to_bits(),
+            options.start_cap as u32,
+            options.end_cap as u32,
+            options.line_join as u32,
+        ]
+    };
+
+    match mode {
+        DrawMode::Fill(options) => [vec![0], fill(options)].concat(),
+        DrawMode::Stroke(options) => [vec![1], stroke(options)].concat(),
+        DrawMode::Outlined {
+            fill_options,
+            outline_options,
+        } => [vec![2], fill(fill_options), stroke(outline_options)].concat(),
+    }
+}
+
+/// The lengths of the sub-paths of a stroked path, used to find how far along
+/// its sub-path a stroke vertex is.
+struct SubpathLengths {
+    /// The first endpoint of every sub-path.
+    starts: Vec<u32>,
+    lengths: Vec<f32>,
+}
+
+impl SubpathLengths {
+    fn new(path: &Path, tolerance: f32) -> Self {
+        let starts = path
+            .id_iter()
+            .filter_map(|event| match event {
+                IdEvent::Begin { at } => Some(at.0),
+                _ => None,
+            })
+            .collect();
+        let lengths = flatten(path, tolerance)
+            .iter()
+            .map(|polyline| {
+                let points = &polyline.points;
+                let closing = match (points.first(), points.last()) {
+                    (Some(&first), Some(&last)) if polyline.closed => (first - last).length(),
+                    _ => 0.0,
+                };
+                points
+                    .windows(2)
+                    .map(|pair| (pair[1] - pair[0]).length())
+                    .sum::<f32>()
+                    + closing
+            })
+            .collect();
+
+        Self { starts, lengths }
+    }
+
+    /// Returns the fraction of the length of its sub-path at which the vertex
+    /// lies.
+    fn fraction(&self, vertex: &StrokeVertex) -> f32 {
+        let id = match vertex.source() {
+            VertexSource::Endpoint { id } | VertexSource::Edge { from: id, .. } => id,
+        };
+        let subpath = match self.starts.binary_search(&id.0) {
+            Ok(index) => index,
+            Err(index) => index.saturating_sub(1),
+        };
+        match self.lengths.get(subpath) {
+            Some(&length) if length > 0.0 => (vertex.advancement() / length).clamp(0.0, 1.0),
+            _ => 0.0,
+        }
+    }
+}
+
+/// Type used to implement various vertex construction traits from Lyon.
+/// Vertices get their color from the gradient if there is one, then they are
+/// passed to the modifier.
+///
+/// It can be used with a lyon `BuffersBuilder` to tessellate shapes outside of
+/// the [`ShapePlugin`]. See [`build_mesh`] for an example.
+pub struct VertexConstructor<'a> {
     color: Color,
+    gradient: Option<&'a Gradient>,
+    modifier: Option<&'a VertexModifier>,
+    uv: UvFrame,
+    width_profile: Option<&'a WidthProfile>,
+    /// The line width of the stroke being tessellated.
+    line_width: f32,
+    /// Only set when stroking with a [`Gradient::Stroke`] or a
+    /// [`WidthProfile`].
+    lengths: Option<SubpathLengths>,
+}
+
+impl<'a> VertexConstructor<'a> {
+    /// Creates a vertex constructor giving the same color to all the vertices.
+    /// Their UV coordinates are computed from their positions, with one unit
+    /// per texture.
+    #[must_use]
+    pub fn new(color: Color) -> Self {
+        let uv = UvFrame {
+            transform: None,
+            origin: Vec2::ZERO,
+            size: Vec2::ONE,
+        };
+        Self::with_options(color, None, None, uv, None)
+    }
+
+    /// Takes the color of the vertices from the given gradient.
+    #[must_use]
+    pub const fn with_gradient(mut self, gradient: &'a Gradient) -> Self {
+        self.gradient = Some(gradient);
+        self
+    }
+
+    /// Passes every vertex to the given modifier.
+    #[must_use]
+    pub const fn with_modifier(mut self, modifier: &'a VertexModifier) -> Self {
+        self.modifier = Some(modifier);
+        self
+    }
+
+    const fn with_options(
+        color: Color,
+        gradient: Option<&'a Gradient>,
+        modifier: Option<&'a VertexModifier>,
+        uv: UvFrame,
+        width_profile: Option<&'a WidthProfile>,
+    ) -> Self {
+        Self {
+            color,
+            gradient,
+            modifier,
+            uv,
+            width_profile,
+            line_width: 0.0,
+            lengths: None,
+        }
+    }
+
+    /// Prepares the constructor to stroke the given path.
+    fn stroking(mut self, path: &Path, tolerance: f32, line_width: f32) -> Self {
+        if matches!(self.gradient, Some(Gradient::Stroke { .. })) || self.width_profile.is_some() {
+            self.lengths = Some(SubpathLengths::new(path, tolerance));
+        }
+        self.line_width = line_width;
+        self
+    }
+
+    /// Returns the path split at the stops of a [`Gradient::Stroke`], so the
+    /// stroke has vertices where the gradient reaches them.
+    fn split_at_stops(&self, path: &Path, tolerance: f32) -> Option<Path> {
+        match self.gradient {
+            Some(Gradient::Stroke { stops }) => {
+                let offsets: Vec<f32> = stops.iter().map(|stop| stop.offset).collect();
+                Some(path_ops::split_at_fractions(path, &offsets, tolerance))
+            }
+            _ => None,
+        }
+    }
+
+    fn vertex(&self, position: Point, fraction: Option<f32>) -> Vertex {
+        let color = match (self.gradient, fraction) {
+            (Some(gradient), Some(fraction)) if matches!(gradient, Gradient::Stroke { .. }) => {
+                gradient.color_along(fraction)
+            }
+            (Some(gradient), _) => gradient.color_at(position.convert()),
+            (None, _) => self.color,
+        };
+        let mut vertex = Vertex {
+            position: [position.x, position.y],
+            color: [color.r(), color.g(), color.b(), color.a()],
+            uv: self.uv.uv(position),
+        };
+        if let Some(modifier) = self.modifier {
+            (modifier.0)(&mut vertex);
+        }
+
+        vertex
+    }
 }
 
 /// Enables the construction of a [`Vertex`] when using a `FillTessellator`.
-impl FillVertexConstructor<Vertex> for VertexConstructor {
+impl FillVertexConstructor<Vertex> for VertexConstructor<'_> {
     fn new_vertex(&mut self, vertex: FillVertex) -> Vertex {
-        Vertex {
-            position: [vertex.position().x, vertex.position().y],
-            color: [
-                self.color.r(),
-                self.color.g(),
-                self.color.b(),
-                self.color.a(),
-            ],
-        }
+        self.vertex(vertex.position(), None)
     }
 }
 
 /// Enables the construction of a [`Vertex`] when using a `StrokeTessellator`.
-impl StrokeVertexConstructor<Vertex> for VertexConstructor {
+impl StrokeVertexConstructor<Vertex> for VertexConstructor<'_> {
     fn new_vertex(&mut self, vertex: StrokeVertex) -> Vertex {
-        Vertex {
-            position: [vertex.position().x, vertex.position().y],
-            color: [
-                self.color.r(),
-                self.color.g(),
-                self.color.b(),
-                self.color.a(),
-            ],
-        }
+        let fraction = self
+            .lengths
+            .as_ref()
+            .map(|lengths| lengths.fraction(&vertex));
+        let position = match (self.width_profile, fraction) {
+            (Some(profile), Some(fraction)) if self.line_width > 0.0 => {
+                let width = (profile.0)(fraction).max(0.0);
+                let on_path = vertex.position_on_path();
+                on_path + (vertex.position() - on_path) * (width / self.line_width)
+            }
+            _ => vertex.position(),
+        };
+        self.vertex(position, fraction)
     }
 }
 
 /// A plugin that provides resources and a system to draw shapes in Bevy with
 /// less boilerplate.
+///
+/// Its systems run in the [`Stage::Shape`] stage, which is added after
+/// `POST_UPDATE`. Use [`in_stage`](Self::in_stage) to run them in another
+/// stage.
 pub struct ShapePlugin;
 
+impl ShapePlugin {
+    /// Returns a plugin adding the systems of the `ShapePlugin` to the given
+    /// stage instead of [`Stage::Shape`], which is then not created.
+    ///
+    /// The stage can be an existing one, or one created by the app before
+    /// adding the plugin. It must run after `POST_UPDATE` and before
+    /// rendering: some systems of the plugin, and the
+    /// [`DynamicShapePlugin`], change the shapes in `POST_UPDATE`, and shapes
+    /// spawned up to `POST_UPDATE` are then drawn in the frame they are
+    /// spawned. Inside the stage, the systems of the app can be ordered
+    /// relative to the [`ShapeSystem`] labels.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_prototype_lyon::prelude::*;
+    ///
+    /// #[derive(Debug, Clone, PartialEq, Eq, Hash, StageLabel)]
+    /// struct ShapeStage;
+    ///
+    /// App::build()
+    ///     .add_plugins(DefaultPlugins)
+    ///     .add_stage_after(CoreStage::PostUpdate, ShapeStage, SystemStage::parallel())
+    ///     .add_plugin(ShapePlugin::in_stage(ShapeStage));
+    /// ```
+    #[must_use]
+    pub fn in_stage<S: StageLabel + Clone>(stage: S) -> StagedShapePlugin<S> {
+        StagedShapePlugin(stage)
+    }
+}
+
 impl Plugin for ShapePlugin {
     fn build(&self, app: &mut AppBuilder) {
-        let fill_tess = FillTessellator::new();
-        let stroke_tess = StrokeTessellator::new();
-        app.insert_resource(fill_tess)
-            .insert_resource(stroke_tess)
-            .add_stage_after(
-                bevy::app::CoreStage::Update,
-                Stage::Shape,
-                SystemStage::parallel(),
-            )
-            .add_startup_system(crate::render::add_shape_pipeline.system())
-            .add_system_to_stage(Stage::Shape, complete_shape_bundle.system());
+        app.add_stage_after(CoreStage::PostUpdate, Stage::Shape, SystemStage::parallel());
+        add_shape_systems(app, Stage::Shape);
+    }
+}
+
+/// A [`ShapePlugin`] running its systems in a stage chosen by the app. Created
+/// by [`ShapePlugin::in_stage`].
+pub struct StagedShapePlugin<S>(S);
+
+impl<S: StageLabel + Clone> Plugin for StagedShapePlugin<S> {
+    fn build(&self, app: &mut AppBuilder) {
+        add_shape_systems(app, self.0.clone());
+    }
+}
+
+/// A plugin keeping the [`Path`] of the shapes with a `T` component in sync
+/// with it, so they can be animated by changing the component.
+///
+/// Insert the geometry as a component on an entity spawned with a
+/// [`ShapeBundle`](crate::entity::ShapeBundle). Every time the component
+/// changes, its path is rebuilt in `POST_UPDATE`, and the shape is then
+/// tessellated again. Add this plugin once for every geometry type to retain.
+///
+/// # Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_prototype_lyon::{plugin::DynamicShapePlugin, prelude::*};
+///
+/// fn main() {
+///     App::build()
+///         .add_plugins(DefaultPlugins)
+///         .add_plugin(ShapePlugin)
+///         .add_plugin(DynamicShapePlugin::<shapes::Circle>::default())
+///         .add_startup_system(setup.system())
+///         .add_system(grow.system());
+/// }
+///
+/// fn setup(mut commands: Commands) {
+///     let circle = shapes::Circle::default();
+///     commands
+///         .spawn_bundle(GeometryBuilder::build_as(
+///             &circle,
+///             ShapeColors::new(Color::PINK),
+///             DrawMode::Fill(FillOptions::default()),
+///             Transform::default(),
+///         ))
+///         .insert(circle);
+/// }
+///
+/// fn grow(time: Res<Time>, mut query: Query<&mut shapes::Circle>) {
+///     for mut circle in query.iter_mut() {
+///         circle.radius += 10.0 * time.delta_seconds();
+///     }
+/// }
+/// ```
+pub struct DynamicShapePlugin<T>(PhantomData<T>);
+
+impl<T> Default for DynamicShapePlugin<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: Geometry + Component> Plugin for DynamicShapePlugin<T> {
+    fn build(&self, app: &mut AppBuilder) {
+        app.add_system_to_stage(CoreStage::PostUpdate, update_dynamic_shapes::<T>.system());
+    }
+}
+
+/// A bevy system. Rebuilds the path of the shapes whose `T` component has
+/// changed.
+fn update_dynamic_shapes<T: Geometry + Component>(mut query: Query<(&T, &mut Path), Changed<T>>) {
+    for (shape, mut path) in query.iter_mut() {
+        *path = to_path(shape);
+    }
+}
+
+/// Adds the resources and the systems of the plugin, running in `stage`.
+fn add_shape_systems(app: &mut AppBuilder, stage: impl StageLabel + Clone) {
+    let fill_tess = FillTessellator::new();
+    let stroke_tess = StrokeTessellator::new();
+    app.insert_resource(fill_tess)
+        .insert_resource(stroke_tess)
+        .init_resource::<MeshCache>()
+        .init_resource::<ShapeSettings>()
+        .add_startup_system(crate::render::add_shape_pipeline.system())
+        .add_startup_system(crate::render::add_shape_alpha_node.system())
+        .add_system_to_stage(
+            stage.clone(),
+            complete_shape_bundle
+                .system()
+                .label(ShapeSystem::Tessellation)
+                .with_run_criteria(shapes_need_meshes.system()),
+        )
+        .add_system_set_to_stage(
+            stage.clone(),
+            SystemSet::new()
+                .before(ShapeSystem::Tessellation)
+                .with_system(retessellate_removed_options.system())
+                .with_system(
+                    update_shape_colors
+                        .system()
+                        .with_run_criteria(shape_colors_changed.system()),
+                ),
+        )
+        .add_system_to_stage(stage.clone(), init_global_transforms.system())
+        .add_system_to_stage(stage.clone(), prune_mesh_cache.system())
+        .add_system_to_stage(stage.clone(), complete_sdf_bundle.system())
+        .add_system_to_stage(stage.clone(), apply_blend_modes.system())
+        .add_system_to_stage(stage.clone(), update_wireframes.system())
+        .add_system_to_stage(
+            stage,
+            propagate_shape_alpha
+                .system()
+                .with_run_criteria(shape_alphas_changed.system()),
+        );
+    add_post_update_systems(app);
+}
+
+/// Adds the systems of the plugin running in `POST_UPDATE`, around the
+/// computation of the global transforms.
+fn add_post_update_systems(app: &mut AppBuilder) {
+    app
+        // These systems change transforms, or draw modes that screen-space
+        // strokes then adjust, so they run before the global transforms are
+        // computed.
+        .add_system_set_to_stage(
+            CoreStage::PostUpdate,
+            SystemSet::new()
+                .before(TransformSystem::TransformPropagate)
+                .with_system(apply_z_index.system())
+                .with_system(snap_translations.system())
+                .with_system(apply_shape_settings.system()),
+        )
+        // The line widths depend on the global transforms, and must be set
+        // before the run criteria of the tessellation are evaluated.
+        .add_system_to_stage(
+            CoreStage::PostUpdate,
+            update_screen_space_strokes
+                .system()
+                .after(TransformSystem::TransformPropagate),
+        );
+}
+
+/// A run criteria. Skips the tessellation, and the borrow of the mesh assets,
+/// when no shape needs a new mesh.
+fn shapes_need_meshes(query: Query<&Path, ShapeChanged>, removed: RemovedOptions<'_>) -> ShouldRun {
+    if query.iter().next().is_some() || removed_options(&removed).next().is_some() {
+        ShouldRun::Yes
+    } else {
+        ShouldRun::No
+    }
+}
+
+/// A run criteria. Skips [`update_shape_colors`] when no colors have changed.
+fn shape_colors_changed(query: Query<&VertexColorSources, Changed<ShapeColors>>) -> ShouldRun {
+    if query.iter().next().is_some() {
+        ShouldRun::Yes
+    } else {
+        ShouldRun::No
+    }
+}
+
+/// A run criteria. Skips [`propagate_shape_alpha`] when no [`ShapeAlpha`] and
+/// no hierarchy has changed, and no shape has been spawned.
+#[allow(clippy::type_complexity)]
+fn shape_alphas_changed(
+    query: Query<
+        Entity,
+        Or<(
+            Changed<ShapeAlpha>,
+            Added<GlobalShapeAlpha>,
+            Changed<Parent>,
+            Changed<Children>,
+        )>,
+    >,
+    removed_alphas: RemovedComponents<ShapeAlpha>,
+    removed_parents: RemovedComponents<Parent>,
+) -> ShouldRun {
+    if query.iter().next().is_some()
+        || removed_alphas.iter().next().is_some()
+        || removed_parents.iter().next().is_some()
+    {
+        ShouldRun::Yes
+    } else {
+        ShouldRun::No
     }
 }
 
@@ -116,54 +1342,798 @@ impl Plugin for ShapePlugin {
 /// mesh.
 #[allow(clippy::type_complexity)]
 fn complete_shape_bundle(
+    mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
+    mut cache: ResMut<MeshCache>,
     mut fill_tess: ResMut<FillTessellator>,
     mut stroke_tess: ResMut<StrokeTessellator>,
     mut query: Query<
         (
+            Entity,
             &DrawMode,
             &Path,
             &mut Handle<Mesh>,
             &ShapeColors,
+            &Transform,
+            VertexOptions<'_>,
+            MeshOptions<'_>,
             &mut Visible,
         ),
-        Added<Path>,
+        ShapeChanged,
     >,
 ) {
-    for (tess_mode, path, mut mesh, colors, mut visible) in query.iter_mut() {
-        let mut buffers = VertexBuffers::new();
+    for (
+        entity,
+        tess_mode,
+        path,
+        mut mesh,
+        colors,
+        transform,
+        vertex_options,
+        mesh_options,
+        mut visible,
+    ) in query.iter_mut()
+    {
+        let key = MeshCache::key(path, tess_mode, colors, vertex_options, mesh_options);
+        let mut entity_commands = commands.entity(entity);
+        if let Some((handle, sources)) = key.as_ref().and_then(|key| cache.get(&meshes, key)) {
+            *mesh = handle;
+            entity_commands.insert_bundle((VertexColorSources(sources), SharedMesh));
+            visible.is_visible = true;
+            continue;
+        }
+        let path = match checked_path(path) {
+            Some(path) => path,
+            None => continue,
+        };
+
+        let uv = UvFrame::new(
+            vertex_options.2.copied().unwrap_or_default(),
+            &path,
+            transform,
+        );
+        let (buffers, sources) = shape_buffers(
+            (&mut fill_tess, &mut stroke_tess),
+            &path,
+            tess_mode,
+            colors,
+            uv,
+            vertex_options,
+            mesh_options,
+        );
+
+        *mesh = meshes.add(shape_mesh(&buffers, mesh_options.5));
+        if let Some(key) = key {
+            cache.0.insert(key, (mesh.clone_weak(), sources.clone()));
+            entity_commands.insert(SharedMesh);
+        } else {
+            entity_commands.remove::<SharedMesh>();
+        }
+        entity_commands.insert(VertexColorSources(sources));
+        visible.is_visible = true;
+    }
+}
+
+/// Returns the path to tessellate, without its zero-length sub-paths, or
+/// `None` if it can't be tessellated. The problems found are logged.
+fn checked_path(path: &Path) -> Option<Cow<'_, Path>> {
+    let path = match validate(path) {
+        Ok(()) => return Some(Cow::Borrowed(path)),
+        Err(e @ PathError::ZeroLength { .. }) => {
+            warn!("Ignoring zero-length sub-paths: {}", e);
+            remove_zero_length(path)
+        }
+        Err(e) => {
+            error!("Invalid path: {}", e);
+            return None;
+        }
+    };
+
+    match validate(&path) {
+        Ok(()) => Some(Cow::Owned(path)),
+        Err(e) => {
+            error!("Invalid path: {}", e);
+            None
+        }
+    }
+}
 
-        match tess_mode {
-            DrawMode::Fill(options) => {
-                fill(&mut fill_tess, path, options, &mut buffers, colors.main);
+/// Builds the vertices and the triangles of a shape, along with the
+/// [`ColorSource`] of each vertex.
+fn shape_buffers(
+    (fill_tess, stroke_tess): (&mut ResMut<FillTessellator>, &mut ResMut<StrokeTessellator>),
+    path: &Path,
+    mode: &DrawMode,
+    colors: &ShapeColors,
+    uv: UvFrame,
+    (gradient, modifier, _, width_profile): VertexOptions<'_>,
+    (dash, feathering, shadow, pixel_snap, merged, _): MeshOptions<'_>,
+) -> (VertexBuffers, Vec<ColorSource>) {
+    if let Some(merged) = merged {
+        let buffers = tessellate_merged(fill_tess, stroke_tess, merged, uv);
+        let sources = vec![ColorSource::Fixed; buffers.vertices.len()];
+        return (buffers, sources);
+    }
+
+    let main = VertexConstructor::with_options(colors.main, gradient, modifier, uv, width_profile);
+    let outline =
+        VertexConstructor::with_options(colors.outline, None, modifier, uv, width_profile);
+    let (mut buffers, mut outline_start) =
+        tessellate(fill_tess, stroke_tess, path, mode, dash, main, outline);
+    if let Some(gradient) = gradient {
+        refine(
+            &mut buffers,
+            &mut outline_start,
+            gradient,
+            modifier.is_none(),
+        );
+    }
+
+    let (main_source, outline_source) = match (modifier, gradient) {
+        (Some(_), _) => (ColorSource::Fixed, ColorSource::Fixed),
+        (None, Some(_)) => (ColorSource::Fixed, ColorSource::Outline(1.0)),
+        (None, None) => (ColorSource::Main(1.0), ColorSource::Outline(1.0)),
+    };
+    let (first_index, first_outline_vertex) = outline_start.unwrap_or((0, buffers.vertices.len()));
+    let mut sources: Vec<ColorSource> = (0..buffers.vertices.len())
+        .map(|index| {
+            if index < first_outline_vertex {
+                main_source
+            } else {
+                outline_source
             }
-            DrawMode::Stroke(options) => {
-                stroke(&mut stroke_tess, path, options, &mut buffers, colors.main);
+        })
+        .collect();
+
+    if let Some(feathering) = feathering {
+        feather(&mut buffers, &mut sources, first_index, feathering.0);
+    }
+    if let Some(shadow) = shadow {
+        let shadow_buffers = drop_shadow(fill_tess, stroke_tess, path, mode, shadow, uv);
+        sources = iter::repeat(ColorSource::Fixed)
+            .take(shadow_buffers.vertices.len())
+            .chain(sources)
+            .collect();
+        buffers = concatenate(shadow_buffers, buffers);
+    }
+    if let Some(pixel_snap) = pixel_snap {
+        pixel_snap.snap_vertices(&mut buffers);
+    }
+
+    (buffers, sources)
+}
+
+/// A bevy system. Writes the [`ShapeColors`] of the shapes whose colors have
+/// changed into the vertices of their mesh.
+///
+/// It runs before the tessellation, so the sources always describe the mesh
+/// they were built with. A [`SharedMesh`] is copied first, so the other shapes
+/// using it keep their colors.
+#[allow(clippy::type_complexity)]
+fn update_shape_colors(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut query: Query<
+        (
+            Entity,
+            &ShapeColors,
+            &mut Handle<Mesh>,
+            &VertexColorSources,
+            Option<&SharedMesh>,
+        ),
+        Changed<ShapeColors>,
+    >,
+) {
+    for (entity, colors, mut handle, sources, shared) in query.iter_mut() {
+        if shared.is_some() {
+            let copy = match meshes.get(&*handle) {
+                Some(mesh) => mesh.clone(),
+                None => continue,
+            };
+            *handle = meshes.add(copy);
+            commands.entity(entity).remove::<SharedMesh>();
+        }
+        let mesh = match meshes.get_mut(&*handle) {
+            Some(mesh) => mesh,
+            None => continue,
+        };
+        let vertex_colors: Vec<[f32; 4]> = match mesh.attribute(Mesh::ATTRIBUTE_COLOR) {
+            Some(VertexAttributeValues::Float4(values)) if values.len() == sources.0.len() => {
+                values
+                    .iter()
+                    .zip(&sources.0)
+                    .map(|(&current, source)| source.color(colors, current))
+                    .collect()
             }
-            DrawMode::Outlined {
+            _ => continue,
+        };
+        mesh.set_attribute(Mesh::ATTRIBUTE_COLOR, vertex_colors);
+    }
+}
+
+/// A bevy system. Removes the freed meshes from the [`MeshCache`].
+fn prune_mesh_cache(mut events: EventReader<AssetEvent<Mesh>>, mut cache: ResMut<MeshCache>) {
+    for event in events.iter() {
+        if let AssetEvent::Removed { handle } = event {
+            cache.0.retain(|_, (cached, _)| cached.id != handle.id);
+        }
+    }
+}
+
+/// A bevy system. Marks the draw mode of the shapes that lost an optional
+/// component as changed, so they are tessellated again without it.
+fn retessellate_removed_options(removed: RemovedOptions<'_>, mut modes: Query<&mut DrawMode>) {
+    for entity in removed_options(&removed) {
+        if let Ok(mut mode) = modes.get_mut(entity) {
+            let current = *mode;
+            *mode = current;
+        }
+    }
+}
+
+/// A bevy system. Gives the shapes with a [`DefaultDrawMode`] the draw mode of
+/// the [`ShapeSettings`], when the component or the settings change.
+fn apply_shape_settings(
+    settings: Res<ShapeSettings>,
+    mut shapes: Query<(
+        ChangeTrackers<DefaultDrawMode>,
+        &DefaultDrawMode,
+        &mut DrawMode,
+    )>,
+) {
+    for (tracker, &default, mut mode) in shapes.iter_mut() {
+        if !settings.is_changed() && !tracker.is_changed() {
+            continue;
+        }
+
+        // Only borrowing the mode mutably when it changes avoids
+        // re-tessellating the shape.
+        let new = settings.draw_mode(default);
+        if *mode != new {
+            *mode = new;
+        }
+    }
+}
+
+/// A bevy system. Updates the line width of the shapes with a
+/// [`ScreenSpaceStroke`], according to the 2D camera.
+fn update_screen_space_strokes(
+    cameras: Query<(&Camera, &OrthographicProjection, &GlobalTransform)>,
+    mut shapes: Query<(&ScreenSpaceStroke, &GlobalTransform, &mut DrawMode)>,
+) {
+    let pixel_size = match cameras
+        .iter()
+        .find(|(camera, ..)| camera.name.as_deref() == Some(CAMERA_2D))
+    {
+        Some((_, projection, transform)) => projection.scale * transform.scale.x.abs(),
+        None => return,
+    };
+
+    for (stroke, transform, mut mode) in shapes.iter_mut() {
+        let scale = transform.scale.x.abs();
+        if scale <= 0.0 {
+            continue;
+        }
+        let width = stroke.0 * pixel_size / scale;
+
+        // Only borrowing the mode mutably when the width changes avoids
+        // re-tessellating the shape every frame.
+        let current = match *mode {
+            DrawMode::Fill(_) => continue,
+            DrawMode::Stroke(options)
+            | DrawMode::Outlined {
+                outline_options: options,
+                ..
+            } => options.line_width,
+        };
+        if (current - width).abs() <= f32::EPSILON * width {
+            continue;
+        }
+        match &mut *mode {
+            DrawMode::Stroke(options)
+            | DrawMode::Outlined {
+                outline_options: options,
+                ..
+            } => options.line_width = width,
+            DrawMode::Fill(_) => {}
+        }
+    }
+}
+
+/// A bevy system. Rounds the translation of the shapes with a [`PixelSnap`].
+fn snap_translations(mut query: Query<(&PixelSnap, &mut Transform), Changed<Transform>>) {
+    for (pixel_snap, mut transform) in query.iter_mut() {
+        let (x, y) = (transform.translation.x, transform.translation.y);
+        let snapped = (pixel_snap.snap(x), pixel_snap.snap(y));
+        // Only writing a different translation avoids marking the transform
+        // as changed every frame.
+        if (snapped.0 - x).abs() > 0.0 || (snapped.1 - y).abs() > 0.0 {
+            transform.translation.x = snapped.0;
+            transform.translation.y = snapped.1;
+        }
+    }
+}
+
+/// A bevy system. Sets the [`GlobalTransform`] of the shapes spawned without a
+/// parent since the last propagation of the transforms, like the ones spawned
+/// in `POST_UPDATE`, so they are not drawn at the origin for one frame.
+#[allow(clippy::type_complexity)]
+fn init_global_transforms(
+    mut query: Query<
+        (&Transform, &mut GlobalTransform),
+        (Or<(Added<Path>, Added<SdfPrimitive>)>, Without<Parent>),
+    >,
+) {
+    for (transform, mut global_transform) in query.iter_mut() {
+        *global_transform = GlobalTransform::from(*transform);
+    }
+}
+
+/// A bevy system. Copies the [`ZIndex`] of the shapes into their
+/// [`Transform`].
+fn apply_z_index(mut query: Query<(&ZIndex, &mut Transform), Changed<ZIndex>>) {
+    for (z_index, mut transform) in query.iter_mut() {
+        transform.translation.z = z_index.0;
+    }
+}
+
+/// A bevy system. Multiplies the [`ShapeAlpha`] of every entity with the ones
+/// of its ancestors, storing the result in its [`GlobalShapeAlpha`].
+///
+/// It only runs in the frames where [`shape_alphas_changed`], since the
+/// alphas can't change otherwise.
+fn propagate_shape_alpha(
+    roots: Query<Entity, Without<Parent>>,
+    children: Query<&Children>,
+    alphas: Query<&ShapeAlpha>,
+    mut globals: Query<&mut GlobalShapeAlpha>,
+) {
+    for root in roots.iter() {
+        propagate_alpha(root, 1.0, &children, &alphas, &mut globals);
+    }
+}
+
+fn propagate_alpha(
+    entity: Entity,
+    parent_alpha: f32,
+    children: &Query<&Children>,
+    alphas: &Query<&ShapeAlpha>,
+    globals: &mut Query<&mut GlobalShapeAlpha>,
+) {
+    let alpha = parent_alpha * alphas.get(entity).map_or(1.0, |alpha| alpha.0);
+    if let Ok(mut global) = globals.get_mut(entity) {
+        // Only writing a different value keeps the uniform from being
+        // uploaded every frame.
+        if (global.value - alpha).abs() > f32::EPSILON {
+            global.value = alpha;
+        }
+    }
+
+    if let Ok(entity_children) = children.get(entity) {
+        for &child in entity_children.iter() {
+            propagate_alpha(child, alpha, children, alphas, globals);
+        }
+    }
+}
+
+/// Tessellates a path according to the draw mode. `main` builds the vertices
+/// of the fill, or of the stroke in [`DrawMode::Stroke`], while `outline`
+/// builds the vertices of the outline in [`DrawMode::Outlined`].
+///
+/// In [`DrawMode::Outlined`], also returns the position of the first index and
+/// of the first vertex of the outline.
+fn tessellate(
+    fill_tess: &mut ResMut<FillTessellator>,
+    stroke_tess: &mut ResMut<StrokeTessellator>,
+    path: &Path,
+    mode: &DrawMode,
+    dash: Option<&StrokeDash>,
+    main: VertexConstructor<'_>,
+    outline: VertexConstructor<'_>,
+) -> (VertexBuffers, Option<(usize, usize)>) {
+    let mut buffers = VertexBuffers::new();
+    let mut outline_start = None;
+    let dashed = |options: &StrokeOptions| {
+        dash.map(|dash| path_ops::dash(path, &dash.pattern, dash.offset, options.tolerance))
+    };
+    match mode {
+        DrawMode::Fill(options) => {
+            fill(fill_tess, path, options, &mut buffers, main);
+        }
+        DrawMode::Stroke(options) => {
+            let dashed_path = dashed(options);
+            let path = dashed_path.as_ref().unwrap_or(path);
+            let split_path = main.split_at_stops(path, options.tolerance);
+            let path = split_path.as_ref().unwrap_or(path);
+            let main = main.stroking(path, options.tolerance, options.line_width);
+            stroke(stroke_tess, path, options, &mut buffers, main);
+        }
+        DrawMode::Outlined {
+            fill_options,
+            outline_options,
+        } => {
+            fill(fill_tess, path, fill_options, &mut buffers, main);
+            outline_start = Some((buffers.indices.len(), buffers.vertices.len()));
+            let dashed_path = dashed(outline_options);
+            let path = dashed_path.as_ref().unwrap_or(path);
+            let outline =
+                outline.stroking(path, outline_options.tolerance, outline_options.line_width);
+            stroke(stroke_tess, path, outline_options, &mut buffers, outline);
+        }
+    }
+
+    (buffers, outline_start)
+}
+
+/// Tessellates the shadow of a path, filling the silhouette of the draw mode
+/// with the shadow color.
+fn drop_shadow(
+    fill_tess: &mut ResMut<FillTessellator>,
+    stroke_tess: &mut ResMut<StrokeTessellator>,
+    path: &Path,
+    mode: &DrawMode,
+    shadow: &DropShadow,
+    uv: UvFrame,
+) -> VertexBuffers {
+    let constructor = || VertexConstructor::with_options(shadow.color, None, None, uv, None);
+
+    let mut buffers = VertexBuffers::new();
+    match mode {
+        DrawMode::Fill(options) => fill(fill_tess, path, options, &mut buffers, constructor()),
+        DrawMode::Stroke(options) => {
+            stroke(stroke_tess, path, options, &mut buffers, constructor());
+        }
+        DrawMode::Outlined {
+            fill_options,
+            outline_options,
+        } => {
+            // Filling and stroking would darken the shadow where the outline
+            // overlaps the fill.
+            let silhouette = path_ops::offset(
+                path,
+                outline_options.line_width / 2.0,
+                outline_options.tolerance,
+            );
+            fill(
+                fill_tess,
+                &silhouette,
                 fill_options,
-                outline_options,
-            } => {
-                fill(
-                    &mut fill_tess,
-                    path,
-                    fill_options,
-                    &mut buffers,
-                    colors.main,
-                );
-                stroke(
-                    &mut stroke_tess,
-                    path,
-                    outline_options,
-                    &mut buffers,
-                    colors.outline,
-                );
+                &mut buffers,
+                constructor(),
+            );
+        }
+    }
+
+    for vertex in &mut buffers.vertices {
+        vertex.position[0] += shadow.offset.x;
+        vertex.position[1] += shadow.offset.y;
+    }
+    if shadow.blur > 0.0 {
+        feather(&mut buffers, 0, shadow.blur);
+    }
+
+    buffers
+}
+
+/// Tessellates the shapes of a [`MergedShapes`] into the same buffers, each
+/// with its own draw mode and colors.
+fn tessellate_merged(
+    fill_tess: &mut ResMut<FillTessellator>,
+    stroke_tess: &mut ResMut<StrokeTessellator>,
+    merged: &MergedShapes,
+    uv: UvFrame,
+) -> VertexBuffers {
+    merged
+        .0
+        .iter()
+        .fold(VertexBuffers::new(), |buffers, shape| {
+            let main = VertexConstructor::with_options(shape.colors.main, None, None, uv, None);
+            let outline =
+                VertexConstructor::with_options(shape.colors.outline, None, None, uv, None);
+            let (shape_buffers, _) = tessellate(
+                fill_tess,
+                stroke_tess,
+                &shape.path,
+                &shape.mode,
+                None,
+                main,
+                outline,
+            );
+            concatenate(buffers, shape_buffers)
+        })
+}
+
+/// Appends the vertices and the triangles of `last` to the ones of `first`.
+fn concatenate(mut first: VertexBuffers, last: VertexBuffers) -> VertexBuffers {
+    let base = first.vertices.len() as IndexType;
+    first.vertices.extend(last.vertices);
+    first
+        .indices
+        .extend(last.indices.iter().map(|index| index + base));
+
+    first
+}
+
+/// Extrudes an alpha-faded rim of the given width around the triangles of
+/// `buffers` starting from `first_index`.
+///
+/// The rim is built on the boundary edges, which belong to only one triangle.
+/// Vertices are compared by position, so the seams between duplicated
+/// vertices are not boundaries. The rim triangles keep the winding of the
+/// triangles they extend, so they are not culled.
+///
+/// Every rim vertex gets the transparent [`ColorSource`] of the vertex it was
+/// extruded from, added to `sources`.
+fn feather(
+    buffers: &mut VertexBuffers,
+    sources: &mut Vec<ColorSource>,
+    first_index: usize,
+    width: f32,
+) {
+    let key = |vertex: &Vertex| (vertex.position[0].to_bits(), vertex.position[1].to_bits());
+    let position = |vertex: &Vertex| Vec2::new(vertex.position[0], vertex.position[1]);
+
+    let edges = boundary_edges(buffers, first_index);
+    let mut normals: BTreeMap<(u32, u32), Vec2> = BTreeMap::new();
+    for &[from, to, opposite] in &edges {
+        let (from, to) = (
+            buffers.vertices[from as usize],
+            buffers.vertices[to as usize],
+        );
+        let edge = position(&to) - position(&from);
+        let inside = position(&buffers.vertices[opposite as usize]) - position(&from);
+        let mut normal = Vec2::new(edge.y, -edge.x).normalize();
+        if normal.dot(inside) > 0.0 {
+            normal = -normal;
+        }
+        *normals.entry(key(&from)).or_insert(Vec2::ZERO) += normal;
+        *normals.entry(key(&to)).or_insert(Vec2::ZERO) += normal;
+    }
+
+    let mut rim: BTreeMap<IndexType, IndexType> = BTreeMap::new();
+    for &[from, to, _] in &edges {
+        let mut rim_index = |index: IndexType| {
+            *rim.entry(index).or_insert_with(|| {
+                let mut vertex = buffers.vertices[index as usize];
+                let normal = normals[&key(&vertex)];
+                if normal.length_squared() > f32::EPSILON {
+                    let offset = normal.normalize() * width;
+                    vertex.position[0] += offset.x;
+                    vertex.position[1] += offset.y;
+                }
+                vertex.color[3] = 0.0;
+                buffers.vertices.push(vertex);
+                sources.push(sources[index as usize].transparent());
+                (buffers.vertices.len() - 1) as IndexType
+            })
+        };
+        let (rim_from, rim_to) = (rim_index(from), rim_index(to));
+        buffers
+            .indices
+            .extend_from_slice(&[to, from, rim_from, to, rim_from, rim_to]);
+    }
+}
+
+/// Splits the triangles of the main part of `buffers`, before the outline
+/// starting at `outline_start`, where the color of the gradient changes, until
+/// their edges are shorter than [`Gradient::max_edge`]. Otherwise, the
+/// gradient would only be sampled at the vertices of the outline for shapes
+/// like circles.
+///
+/// The new vertices are interpolated from the ends of the split edges, and
+/// take the color of the gradient if `recolor` is set. They are inserted
+/// before the outline, whose start is updated.
+fn refine(
+    buffers: &mut VertexBuffers,
+    outline_start: &mut Option<(usize, usize)>,
+    gradient: &Gradient,
+    recolor: bool,
+) {
+    // Enough to split the longest edges of any reasonable shape.
+    const MAX_PASSES: usize = 16;
+    let max_edge = match gradient.max_edge() {
+        Some(max_edge) => max_edge,
+        None => return,
+    };
+
+    let (first_index, first_outline_vertex) =
+        outline_start.unwrap_or((buffers.indices.len(), buffers.vertices.len()));
+    let outline_vertices = buffers.vertices.split_off(first_outline_vertex);
+    let outline_indices = buffers.indices.split_off(first_index);
+
+    let mut triangles: Vec<[IndexType; 3]> = buffers
+        .indices
+        .chunks_exact(3)
+        .map(|t| [t[0], t[1], t[2]])
+        .collect();
+    for _ in 0..MAX_PASSES {
+        let midpoints = split_edges(&mut buffers.vertices, &triangles, gradient, max_edge);
+        if midpoints.is_empty() {
+            break;
+        }
+        let split_at = |from: IndexType, to: IndexType| {
+            midpoints
+                .get(&edge_key(&buffers.vertices, from, to))
+                .copied()
+        };
+        triangles = triangles
+            .iter()
+            .flat_map(|&triangle| split_triangle(triangle, &split_at))
+            .collect();
+    }
+    if recolor {
+        for vertex in &mut buffers.vertices[first_outline_vertex..] {
+            let color = gradient.color_at(Vec2::from(vertex.position));
+            vertex.color = [color.r(), color.g(), color.b(), color.a()];
+        }
+    }
+
+    let shift = (buffers.vertices.len() - first_outline_vertex) as IndexType;
+    buffers.indices = triangles.iter().flatten().copied().collect();
+    if outline_start.is_some() {
+        *outline_start = Some((buffers.indices.len(), buffers.vertices.len()));
+    }
+    buffers.vertices.extend(outline_vertices);
+    buffers
+        .indices
+        .extend(outline_indices.iter().map(|index| index + shift));
+}
+
+/// Returns the key of the edge between two vertices, made of their positions,
+/// so the duplicated vertices of the tessellators share their edges.
+fn edge_key(vertices: &[Vertex], from: IndexType, to: IndexType) -> ((u32, u32), (u32, u32)) {
+    let key = |index: IndexType| {
+        let position = vertices[index as usize].position;
+        (position[0].to_bits(), position[1].to_bits())
+    };
+
+    (key(from).min(key(to)), key(from).max(key(to)))
+}
+
+/// Adds a vertex in the middle of every edge longer than `max_edge` of the
+/// triangles where the gradient changes color, and returns their indices by
+/// edge.
+///
+/// An edge is split for all its triangles, so the split triangles don't leave
+/// cracks between them.
+fn split_edges(
+    vertices: &mut Vec<Vertex>,
+    triangles: &[[IndexType; 3]],
+    gradient: &Gradient,
+    max_edge: f32,
+) -> HashMap<((u32, u32), (u32, u32)), IndexType> {
+    let mut midpoints = HashMap::new();
+    for triangle in triangles {
+        let corners = {
+            let position = |index: IndexType| Vec2::from(vertices[index as usize].position);
+            [
+                position(triangle[0]),
+                position(triangle[1]),
+                position(triangle[2]),
+            ]
+        };
+        if !gradient.varies_in(corners) {
+            continue;
+        }
+        for i in 0..3 {
+            let (from, to) = (triangle[i], triangle[(i + 1) % 3]);
+            let key = edge_key(vertices, from, to);
+            if midpoints.contains_key(&key) || corners[i].distance(corners[(i + 1) % 3]) <= max_edge
+            {
+                continue;
             }
+            vertices.push(midpoint(&vertices[from as usize], &vertices[to as usize]));
+            midpoints.insert(key, (vertices.len() - 1) as IndexType);
         }
+    }
 
-        *mesh = meshes.add(build_mesh(&buffers));
-        visible.is_visible = true;
+    midpoints
+}
+
+/// Returns the vertex halfway between two vertices, with all its attributes
+/// interpolated.
+fn midpoint(a: &Vertex, b: &Vertex) -> Vertex {
+    let mut vertex = *a;
+    let pairs = vertex
+        .position
+        .iter_mut()
+        .zip(&b.position)
+        .chain(vertex.color.iter_mut().zip(&b.color))
+        .chain(vertex.uv.iter_mut().zip(&b.uv));
+    for (value, other) in pairs {
+        *value = (*value + other) / 2.0;
+    }
+
+    vertex
+}
+
+/// Splits a triangle at the midpoints of its edges, keeping its winding.
+fn split_triangle(
+    triangle: [IndexType; 3],
+    midpoint: impl Fn(IndexType, IndexType) -> Option<IndexType>,
+) -> Vec<[IndexType; 3]> {
+    let [a, b, c] = triangle;
+    let midpoints = [midpoint(a, b), midpoint(b, c), midpoint(c, a)];
+    if let [Some(ab), Some(bc), Some(ca)] = midpoints {
+        return vec![[a, ab, ca], [ab, b, bc], [ca, bc, c], [ab, bc, ca]];
+    }
+
+    // Rotate the triangle so that its first edge is split, but not its last
+    // one.
+    let rotation =
+        match (0..3).find(|&i| midpoints[i].is_some() && midpoints[(i + 2) % 3].is_none()) {
+            Some(rotation) => rotation,
+            None => return vec![triangle],
+        };
+    let (a, b, c) = (
+        triangle[rotation],
+        triangle[(rotation + 1) % 3],
+        triangle[(rotation + 2) % 3],
+    );
+    match (midpoints[rotation], midpoints[(rotation + 1) % 3]) {
+        (Some(ab), Some(bc)) => vec![[ab, b, bc], [a, ab, bc], [a, bc, c]],
+        (Some(ab), None) => vec![[a, ab, c], [ab, b, c]],
+        _ => vec![triangle],
+    }
+}
+
+/// Returns the boundary edges of the triangles of `buffers` starting from
+/// `first_index`, as the indices of their endpoints, in the winding order of
+/// their triangle, followed by the index of the opposite vertex.
+fn boundary_edges(buffers: &VertexBuffers, first_index: usize) -> Vec<[IndexType; 3]> {
+    let key = |index: IndexType| {
+        let position = buffers.vertices[index as usize].position;
+        (position[0].to_bits(), position[1].to_bits())
+    };
+    let triangle_edges: Vec<[IndexType; 3]> = buffers.indices[first_index..]
+        .chunks_exact(3)
+        .flat_map(|t| vec![[t[0], t[1], t[2]], [t[1], t[2], t[0]], [t[2], t[0], t[1]]])
+        .filter(|&[from, to, _]| key(from) != key(to))
+        .collect();
+    let edge_key = |from, to| (key(from).min(key(to)), key(from).max(key(to)));
+
+    let mut counts = BTreeMap::new();
+    for &[from, to, _] in &triangle_edges {
+        *counts.entry(edge_key(from, to)).or_insert(0) += 1;
     }
+
+    triangle_edges
+        .into_iter()
+        .filter(|&[from, to, _]| counts[&edge_key(from, to)] == 1)
+        .collect()
+}
+
+/// Returns the bottom-left corner and the size of the bounding box of a path,
+/// including the control points of its curves.
+fn bounds(path: &Path) -> (Point, Vector) {
+    let mut bounds: Option<(Point, Point)> = None;
+    let mut extend = |point: Point| {
+        bounds = Some(bounds.map_or((point, point), |(min, max)| {
+            (min.min(point), max.max(point))
+        }));
+    };
+    for event in path.iter() {
+        match event {
+            PathEvent::Begin { at } => extend(at),
+            PathEvent::Line { to, .. } => extend(to),
+            PathEvent::Quadratic { ctrl, to, .. } => {
+                extend(ctrl);
+                extend(to);
+            }
+            PathEvent::Cubic {
+                ctrl1, ctrl2, to, ..
+            } => {
+                extend(ctrl1);
+                extend(ctrl2);
+                extend(to);
+            }
+            PathEvent::End { .. } => {}
+        }
+    }
+
+    bounds.map_or((Point::zero(), Vector::zero()), |(min, max)| {
+        (min, max - min)
+    })
 }
 
 #[allow(clippy::clippy::trivially_copy_pass_by_ref)] // lyon takes &FillOptions
@@ -172,17 +2142,12 @@ fn fill(
     path: &Path,
     options: &FillOptions,
     buffers: &mut VertexBuffers,
-    vertex_color: Color,
+    vertex_constructor: impl FillVertexConstructor<Vertex>,
 ) {
     if let Err(e) = tess.tessellate_path(
         path,
         options,
-        &mut BuffersBuilder::new(
-            buffers,
-            VertexConstructor {
-                color: vertex_color,
-            },
-        ),
+        &mut BuffersBuilder::new(buffers, vertex_constructor),
     ) {
         error!("FillTessellator error: {:?}", e);
     }
@@ -194,25 +2159,72 @@ fn stroke(
     path: &Path,
     options: &StrokeOptions,
     buffers: &mut VertexBuffers,
-    vertex_color: Color,
+    vertex_constructor: impl StrokeVertexConstructor<Vertex>,
 ) {
     if let Err(e) = tess.tessellate_path(
         path,
         options,
-        &mut BuffersBuilder::new(
-            buffers,
-            VertexConstructor {
-                color: vertex_color,
-            },
-        ),
+        &mut BuffersBuilder::new(buffers, vertex_constructor),
     ) {
         error!("StrokeTessellator error: {:?}", e);
     }
 }
 
-fn build_mesh(buffers: &VertexBuffers) -> Mesh {
-    pub const ATTRIBUTE_POSITION_2D: &str = "Vertex_Position_2D";
+/// Builds the mesh of a shape, with the [`MeshAttributes`] it may have.
+fn shape_mesh(buffers: &VertexBuffers, attributes: Option<&MeshAttributes>) -> Mesh {
+    let mut mesh = build_mesh(buffers);
+    if let Some(attributes) = attributes {
+        (attributes.0)(buffers, &mut mesh);
+    }
+
+    mesh
+}
 
+/// Builds a mesh from tessellated vertex buffers, with the attributes used by
+/// the shape pipelines: [`ATTRIBUTE_POSITION_2D`], the colors and the UV
+/// coordinates.
+///
+/// More attributes can be added to the returned mesh for a custom pipeline.
+/// The shapes drawn by the [`ShapePlugin`] get theirs from their
+/// [`MeshAttributes`].
+///
+/// # Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_prototype_lyon::{
+///     plugin::{build_mesh, VertexBuffers, VertexConstructor},
+///     prelude::*,
+/// };
+/// use lyon_tessellation::{path::Path, BuffersBuilder, FillTessellator};
+///
+/// let circle = shapes::Circle {
+///     radius: 10.0,
+///     ..shapes::Circle::default()
+/// };
+/// let mut path_builder = Path::builder();
+/// circle.add_geometry(&mut path_builder);
+///
+/// let mut buffers = VertexBuffers::new();
+/// FillTessellator::new()
+///     .tessellate_path(
+///         &path_builder.build(),
+///         &FillOptions::default(),
+///         &mut BuffersBuilder::new(&mut buffers, VertexConstructor::new(Color::RED)),
+///     )
+///     .unwrap();
+///
+/// let mut mesh = build_mesh(&buffers);
+/// // The distance of every vertex from the center, for a custom shader.
+/// let distances: Vec<f32> = buffers
+///     .vertices
+///     .iter()
+///     .map(|vertex| Vec2::from(vertex.position).length())
+///     .collect();
+/// mesh.set_attribute("Vertex_Distance", distances);
+/// ```
+#[must_use]
+pub fn build_mesh(buffers: &VertexBuffers) -> Mesh {
     let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
     mesh.set_indices(Some(Indices::U32(buffers.indices.clone())));
     mesh.set_attribute(
@@ -231,6 +2243,243 @@ fn build_mesh(buffers: &VertexBuffers) -> Mesh {
             .map(|v| v.color)
             .collect::<Vec<[f32; 4]>>(),
     );
+    mesh.set_attribute(
+        Mesh::ATTRIBUTE_UV_0,
+        buffers
+            .vertices
+            .iter()
+            .map(|v| v.uv)
+            .collect::<Vec<[f32; 2]>>(),
+    );
 
     mesh
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gradient::GradientStop, shapes::Circle};
+
+    const NO_VERTEX_OPTIONS: VertexOptions<'static> = (None, None, None, None);
+    const NO_MESH_OPTIONS: MeshOptions<'static> = (None, None, None, None, None, None);
+
+    fn vertex(x: f32, y: f32) -> Vertex {
+        Vertex {
+            position: [x, y],
+            color: [1.0; 4],
+            uv: [0.0; 2],
+        }
+    }
+
+    fn signed_area(vertices: &[Vertex], [a, b, c]: [IndexType; 3]) -> f32 {
+        let position = |index: IndexType| Vec2::from(vertices[index as usize].position);
+        let (u, v) = (position(b) - position(a), position(c) - position(a));
+        u.x.mul_add(v.y, -u.y * v.x) / 2.0
+    }
+
+    #[test]
+    fn split_triangle_keeps_the_winding_without_cracks() {
+        // A counter-clockwise triangle, followed by the midpoints of its edges.
+        let vertices = [
+            vertex(0.0, 0.0),
+            vertex(4.0, 0.0),
+            vertex(0.0, 4.0),
+            vertex(2.0, 0.0),
+            vertex(2.0, 2.0),
+            vertex(0.0, 2.0),
+        ];
+        let edges: [(IndexType, IndexType, IndexType); 3] = [(0, 1, 3), (1, 2, 4), (2, 0, 5)];
+
+        for split in 0..8 {
+            let midpoint = |from: IndexType, to: IndexType| {
+                edges
+                    .iter()
+                    .enumerate()
+                    .filter(|&(i, _)| split & (1 << i) != 0)
+                    .find(|&(_, &(a, b, _))| (from, to) == (a, b) || (from, to) == (b, a))
+                    .map(|(_, &(_, _, midpoint))| midpoint)
+            };
+
+            let triangles = split_triangle([0, 1, 2], &midpoint);
+
+            let areas: Vec<f32> = triangles
+                .iter()
+                .map(|&triangle| signed_area(&vertices, triangle))
+                .collect();
+            assert!(areas.iter().all(|&area| area > 0.0), "{:?}", triangles);
+            assert!(
+                (areas.iter().sum::<f32>() - 8.0).abs() < 1e-6,
+                "{:?}",
+                triangles
+            );
+            // A triangle using a split edge would leave a crack next to the
+            // triangles using its midpoint.
+            for &[a, b, c] in &triangles {
+                for &(from, to) in &[(a, b), (b, c), (c, a)] {
+                    assert!(midpoint(from, to).is_none(), "{:?}", triangles);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn refine_keeps_the_outline_after_the_main_part() {
+        // A square made of two triangles, followed by an outline triangle.
+        let mut buffers = VertexBuffers::new();
+        buffers.vertices = vec![
+            vertex(0.0, 0.0),
+            vertex(100.0, 0.0),
+            vertex(100.0, 100.0),
+            vertex(0.0, 100.0),
+            vertex(0.0, 0.0),
+            vertex(100.0, 0.0),
+            vertex(100.0, 100.0),
+        ];
+        buffers.indices = vec![0, 1, 2, 0, 2, 3, 4, 5, 6];
+        let outline = buffers.vertices[4..].to_vec();
+        let mut outline_start = Some((6, 4));
+        let gradient = Gradient::Linear {
+            start: Vec2::ZERO,
+            end: Vec2::new(100.0, 0.0),
+            stops: vec![
+                GradientStop::new(0.0, Color::BLACK),
+                GradientStop::new(1.0, Color::WHITE),
+            ],
+        };
+
+        refine(&mut buffers, &mut outline_start, &gradient, true);
+
+        let (first_index, first_vertex) = outline_start.unwrap();
+        assert!(first_index > 6 && first_vertex > 4);
+        assert!(buffers.indices[..first_index]
+            .iter()
+            .all(|&index| (index as usize) < first_vertex));
+        assert_eq!(buffers.vertices[first_vertex..], outline[..]);
+        let first_vertex = first_vertex as IndexType;
+        assert_eq!(
+            buffers.indices[first_index..],
+            [first_vertex, first_vertex + 1, first_vertex + 2]
+        );
+    }
+
+    #[test]
+    fn feather_gives_the_rim_the_sources_of_its_origins() {
+        // A filled square, followed by an outline triangle.
+        let mut buffers = VertexBuffers::new();
+        buffers.vertices = vec![
+            vertex(0.0, 0.0),
+            vertex(1.0, 0.0),
+            vertex(1.0, 1.0),
+            vertex(0.0, 1.0),
+            vertex(3.0, 0.0),
+            vertex(4.0, 0.0),
+            vertex(4.0, 1.0),
+        ];
+        buffers.indices = vec![0, 1, 2, 0, 2, 3, 4, 5, 6];
+        let mut sources = vec![ColorSource::Main(1.0); 4];
+        sources.extend(vec![ColorSource::Outline(1.0); 3]);
+
+        feather(&mut buffers, &mut sources, 0, 0.5);
+
+        assert_eq!(sources.len(), buffers.vertices.len());
+        for (rim, source) in buffers.vertices[7..].iter().zip(&sources[7..]) {
+            let origin = buffers.vertices[..7]
+                .iter()
+                .zip(&sources)
+                .find(|(vertex, _)| {
+                    Vec2::from(vertex.position).distance(Vec2::from(rim.position)) <= 0.5 + 1e-6
+                })
+                .map(|(_, &source)| source);
+            assert_eq!(origin.map(ColorSource::transparent), Some(*source));
+            assert!(rim.color[3] < f32::EPSILON);
+        }
+    }
+
+    #[test]
+    fn mesh_cache_skips_shapes_with_optional_components() {
+        let path = to_path(&Circle::default());
+        let mode = DrawMode::Fill(FillOptions::default());
+        let colors = ShapeColors::new(Color::RED);
+        let key = |vertex_options, mesh_options| {
+            MeshCache::key(&path, &mode, &colors, vertex_options, mesh_options)
+        };
+        let gradient = Gradient::Stroke { stops: vec![] };
+        let modifier = VertexModifier::new(|_| {});
+        let uv = UvMapping::default();
+        let width = WidthProfile::new(|_| 1.0);
+        let dash = StrokeDash::default();
+        let feathering = Feathering::default();
+        let shadow = DropShadow::default();
+        let snap = PixelSnap::default();
+        let merged = MergedShapes::default();
+        let attributes = MeshAttributes::new(|_, _| {});
+
+        assert!(key(NO_VERTEX_OPTIONS, NO_MESH_OPTIONS).is_some());
+        let vertex_options: [VertexOptions<'_>; 4] = [
+            (Some(&gradient), None, None, None),
+            (None, Some(&modifier), None, None),
+            (None, None, Some(&uv), None),
+            (None, None, None, Some(&width)),
+        ];
+        for &options in &vertex_options {
+            assert!(key(options, NO_MESH_OPTIONS).is_none());
+        }
+        let mesh_options: [MeshOptions<'_>; 6] = [
+            (Some(&dash), None, None, None, None, None),
+            (None, Some(&feathering), None, None, None, None),
+            (None, None, Some(&shadow), None, None, None),
+            (None, None, None, Some(&snap), None, None),
+            (None, None, None, None, Some(&merged), None),
+            (None, None, None, None, None, Some(&attributes)),
+        ];
+        for &options in &mesh_options {
+            assert!(key(NO_VERTEX_OPTIONS, options).is_none());
+        }
+    }
+
+    #[test]
+    fn mesh_cache_keys_differ_with_the_mode_and_the_colors() {
+        let path = to_path(&Circle::default());
+        let key = |mode: DrawMode, colors: ShapeColors| {
+            MeshCache::key(&path, &mode, &colors, NO_VERTEX_OPTIONS, NO_MESH_OPTIONS)
+        };
+        let fill = DrawMode::Fill(FillOptions::default());
+        let stroke = DrawMode::Stroke(StrokeOptions::default());
+        let red = ShapeColors::new(Color::RED);
+
+        assert_eq!(key(fill, red), key(fill, red));
+        assert_ne!(key(fill, red), key(stroke, red));
+        assert_ne!(
+            key(stroke, red),
+            key(
+                DrawMode::Stroke(StrokeOptions::default().with_line_width(2.0)),
+                red
+            )
+        );
+        assert_ne!(key(fill, red), key(fill, ShapeColors::new(Color::BLUE)));
+        assert_ne!(
+            key(fill, red),
+            key(fill, ShapeColors::outlined(Color::RED, Color::BLUE))
+        );
+    }
+
+    #[test]
+    fn color_sources_take_the_new_colors() {
+        let colors = ShapeColors::outlined(Color::RED, Color::BLUE);
+        let current = [0.5; 4];
+
+        assert_eq!(
+            ColorSource::Main(1.0).color(&colors, current),
+            [1.0, 0.0, 0.0, 1.0]
+        );
+        assert_eq!(
+            ColorSource::Outline(1.0).color(&colors, current),
+            [0.0, 0.0, 1.0, 1.0]
+        );
+        assert_eq!(
+            ColorSource::Main(1.0).transparent().color(&colors, current),
+            [1.0, 0.0, 0.0, 0.0]
+        );
+        assert_eq!(ColorSource::Fixed.color(&colors, current), current);
+    }
+}
@@ -2,24 +2,196 @@
 
 use bevy::{
     asset::{Assets, HandleUntyped},
-    ecs::system::ResMut,
+    ecs::{
+        entity::Entity,
+        query::{Added, Changed},
+        system::{Query, RemovedComponents, ResMut},
+    },
     reflect::TypeUuid,
     render::{
         pipeline::{
             BlendFactor, BlendOperation, BlendState, ColorTargetState, ColorWrite, CompareFunction,
             CullMode, DepthBiasState, DepthStencilState, FrontFace, PipelineDescriptor,
-            PolygonMode, PrimitiveState, PrimitiveTopology, StencilFaceState, StencilState,
+            PolygonMode, PrimitiveState, PrimitiveTopology, RenderPipeline, RenderPipelines,
+            StencilFaceState, StencilState,
         },
+        render_graph::{base, RenderGraph, RenderResourcesNode},
         shader::{Shader, ShaderStage, ShaderStages},
         texture::TextureFormat,
     },
 };
 
+use crate::entity::GlobalShapeAlpha;
+
 #[allow(missing_docs, clippy::unreadable_literal)]
 pub const SHAPE_PIPELINE_HANDLE: HandleUntyped =
     HandleUntyped::weak_from_u64(PipelineDescriptor::TYPE_UUID, 3868147544761532180);
 
-fn build_shape_pipeline(mut shaders: ResMut<Assets<Shader>>) -> PipelineDescriptor {
+/// The pipeline used by shapes filled with a
+/// [`ColorMaterial`](bevy::sprite::ColorMaterial), which can have a texture.
+#[allow(clippy::unreadable_literal)]
+pub const TEXTURED_SHAPE_PIPELINE_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(PipelineDescriptor::TYPE_UUID, 1975390437205716394);
+
+/// The pipeline used by [`SdfShapeBundle`](crate::sdf::SdfShapeBundle)s.
+#[allow(clippy::unreadable_literal)]
+pub const SDF_SHAPE_PIPELINE_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(PipelineDescriptor::TYPE_UUID, 6127390658721485993);
+
+/// The name of the render graph node passing the
+/// [`GlobalShapeAlpha`](crate::entity::GlobalShapeAlpha) of the shapes to the
+/// shaders.
+pub const SHAPE_ALPHA_NODE: &str = "shape_alpha";
+
+/// The pipeline used by shapes with [`BlendMode::Additive`].
+#[allow(clippy::unreadable_literal)]
+pub const ADDITIVE_SHAPE_PIPELINE_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(PipelineDescriptor::TYPE_UUID, 8301742956387215046);
+
+/// The pipeline used by shapes with [`BlendMode::Multiply`].
+#[allow(clippy::unreadable_literal)]
+pub const MULTIPLY_SHAPE_PIPELINE_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(PipelineDescriptor::TYPE_UUID, 2547916830452198377);
+
+/// The pipeline drawing the edges of the triangles of shapes with a
+/// [`ShapeWireframe`].
+#[allow(clippy::unreadable_literal)]
+pub const WIREFRAME_SHAPE_PIPELINE_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(PipelineDescriptor::TYPE_UUID, 4470563218916250731);
+
+/// Draws the edges of the triangles of a shape on top of it, to inspect its
+/// tessellation, for example to choose a tolerance.
+///
+/// Insert it as a component on an entity spawned with a
+/// [`ShapeBundle`](crate::entity::ShapeBundle), and remove it to hide the
+/// wireframe. The edges are drawn with the inverse of the colors of the
+/// vertices, so they stand out on the shape.
+///
+/// Drawing lines needs the `NonFillPolygonMode` feature of the GPU, which must
+/// be enabled when the app is built.
+///
+/// # Example
+///
+/// ```no_run
+/// use bevy::{
+///     prelude::*,
+///     wgpu::{WgpuFeature, WgpuFeatures, WgpuOptions},
+/// };
+/// use bevy_prototype_lyon::{prelude::*, render::ShapeWireframe};
+///
+/// fn main() {
+///     App::build()
+///         .insert_resource(WgpuOptions {
+///             features: WgpuFeatures {
+///                 features: vec![WgpuFeature::NonFillPolygonMode],
+///             },
+///             ..WgpuOptions::default()
+///         })
+///         .add_plugins(DefaultPlugins)
+///         .add_plugin(ShapePlugin)
+///         .add_startup_system(setup.system())
+///         .run();
+/// }
+///
+/// fn setup(mut commands: Commands) {
+///     let circle = shapes::Circle {
+///         radius: 100.0,
+///         ..shapes::Circle::default()
+///     };
+///     commands
+///         .spawn_bundle(GeometryBuilder::build_as(
+///             &circle,
+///             ShapeColors::new(Color::YELLOW),
+///             DrawMode::Fill(FillOptions::tolerance(1.0)),
+///             Transform::default(),
+///         ))
+///         .insert(ShapeWireframe);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ShapeWireframe;
+
+/// Determines how the colors of a shape are combined with what is behind it.
+///
+/// Insert it as a component on an entity spawned with a
+/// [`ShapeBundle`](crate::entity::ShapeBundle) to replace its render pipeline
+/// with one using the blend mode. Removing it restores the default
+/// [`BlendMode::Alpha`]. Shapes using other pipelines, like the ones
+/// of [`MaterialShapeBundle`](crate::entity::MaterialShapeBundle), are not
+/// affected: the blend state is part of their own pipeline descriptor.
+///
+/// # Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_prototype_lyon::{prelude::*, render::BlendMode};
+///
+/// fn some_system(mut commands: Commands) {
+///     let glow = shapes::Circle {
+///         radius: 50.0,
+///         ..shapes::Circle::default()
+///     };
+///     commands
+///         .spawn_bundle(GeometryBuilder::build_as(
+///             &glow,
+///             ShapeColors::new(Color::rgba(1.0, 0.8, 0.2, 0.5)),
+///             DrawMode::Fill(FillOptions::default()),
+///             Transform::default(),
+///         ))
+///         .insert(BlendMode::Additive);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BlendMode {
+    /// The standard alpha blending: the shape covers what is behind it
+    /// according to its alpha.
+    Alpha,
+    /// The colors of the shape, multiplied by their alpha, are added to the
+    /// ones behind it, brightening them. Useful for glows and lights.
+    Additive,
+    /// The colors behind the shape are multiplied by the colors of the shape,
+    /// darkening them. Useful for shadows. Works best with opaque colors,
+    /// since transparency makes the result lighter than expected.
+    Multiply,
+}
+
+impl BlendMode {
+    /// Returns the handle of the shape pipeline using the blend mode.
+    #[must_use]
+    pub const fn pipeline(self) -> HandleUntyped {
+        match self {
+            Self::Alpha => SHAPE_PIPELINE_HANDLE,
+            Self::Additive => ADDITIVE_SHAPE_PIPELINE_HANDLE,
+            Self::Multiply => MULTIPLY_SHAPE_PIPELINE_HANDLE,
+        }
+    }
+
+    const fn color_blend(self) -> BlendState {
+        let (src_factor, dst_factor) = match self {
+            Self::Alpha => (BlendFactor::SrcAlpha, BlendFactor::OneMinusSrcAlpha),
+            Self::Additive => (BlendFactor::SrcAlpha, BlendFactor::One),
+            Self::Multiply => (BlendFactor::DstColor, BlendFactor::OneMinusSrcAlpha),
+        };
+
+        BlendState {
+            src_factor,
+            dst_factor,
+            operation: BlendOperation::Add,
+        }
+    }
+}
+
+impl Default for BlendMode {
+    fn default() -> Self {
+        Self::Alpha
+    }
+}
+
+fn build_shape_pipeline(
+    shaders: &mut Assets<Shader>,
+    vertex_shader: &str,
+    fragment_shader: &str,
+) -> PipelineDescriptor {
     PipelineDescriptor {
         depth_stencil: Some(DepthStencilState {
             format: TextureFormat::Depth32Float,
@@ -40,11 +212,7 @@ fn build_shape_pipeline(mut shaders: ResMut<Assets<Shader>>) -> PipelineDescript
         }),
         color_target_states: vec![ColorTargetState {
             format: TextureFormat::default(),
-            color_blend: BlendState {
-                src_factor: BlendFactor::SrcAlpha,
-                dst_factor: BlendFactor::OneMinusSrcAlpha,
-                operation: BlendOperation::Add,
-            },
+            color_blend: BlendMode::Alpha.color_blend(),
             alpha_blend: BlendState {
                 src_factor: BlendFactor::One,
                 dst_factor: BlendFactor::One,
@@ -60,21 +228,118 @@ fn build_shape_pipeline(mut shaders: ResMut<Assets<Shader>>) -> PipelineDescript
             polygon_mode: PolygonMode::Fill,
         },
         ..PipelineDescriptor::new(ShaderStages {
-            vertex: shaders.add(Shader::from_glsl(
-                ShaderStage::Vertex,
-                include_str!("shape.vert"),
-            )),
-            fragment: Some(shaders.add(Shader::from_glsl(
-                ShaderStage::Fragment,
-                include_str!("shape.frag"),
-            ))),
+            vertex: shaders.add(Shader::from_glsl(ShaderStage::Vertex, vertex_shader)),
+            fragment: Some(shaders.add(Shader::from_glsl(ShaderStage::Fragment, fragment_shader))),
         })
     }
 }
 
 pub(crate) fn add_shape_pipeline(
     mut pipelines: ResMut<Assets<PipelineDescriptor>>,
-    shaders: ResMut<Assets<Shader>>,
+    mut shaders: ResMut<Assets<Shader>>,
 ) {
-    pipelines.set_untracked(SHAPE_PIPELINE_HANDLE, build_shape_pipeline(shaders));
+    pipelines.set_untracked(
+        SHAPE_PIPELINE_HANDLE,
+        build_shape_pipeline(
+            &mut shaders,
+            include_str!("shape.vert"),
+            include_str!("shape.frag"),
+        ),
+    );
+    pipelines.set_untracked(
+        TEXTURED_SHAPE_PIPELINE_HANDLE,
+        build_shape_pipeline(
+            &mut shaders,
+            include_str!("textured_shape.vert"),
+            include_str!("textured_shape.frag"),
+        ),
+    );
+
+    // SDF quads are drawn whatever their winding, so mirrored shapes are not
+    // culled.
+    let mut sdf_pipeline = build_shape_pipeline(
+        &mut shaders,
+        include_str!("sdf_shape.vert"),
+        include_str!("sdf_shape.frag"),
+    );
+    sdf_pipeline.primitive.cull_mode = CullMode::None;
+    pipelines.set_untracked(SDF_SHAPE_PIPELINE_HANDLE, sdf_pipeline);
+
+    let mut wireframe_pipeline = build_shape_pipeline(
+        &mut shaders,
+        include_str!("shape.vert"),
+        include_str!("wireframe.frag"),
+    );
+    wireframe_pipeline.primitive.polygon_mode = PolygonMode::Line;
+    wireframe_pipeline.primitive.cull_mode = CullMode::None;
+    pipelines.set_untracked(WIREFRAME_SHAPE_PIPELINE_HANDLE, wireframe_pipeline);
+
+    for &mode in &[BlendMode::Additive, BlendMode::Multiply] {
+        let mut pipeline = build_shape_pipeline(
+            &mut shaders,
+            include_str!("shape.vert"),
+            include_str!("shape.frag"),
+        );
+        pipeline.color_target_states[0].color_blend = mode.color_blend();
+        pipelines.set_untracked(mode.pipeline(), pipeline);
+    }
+}
+
+pub(crate) fn add_shape_alpha_node(mut render_graph: ResMut<RenderGraph>) {
+    render_graph.add_system_node(
+        SHAPE_ALPHA_NODE,
+        RenderResourcesNode::<GlobalShapeAlpha>::new(true),
+    );
+    render_graph
+        .add_node_edge(SHAPE_ALPHA_NODE, base::node::MAIN_PASS)
+        .unwrap();
+}
+
+/// A bevy system. Replaces the shape pipeline of the entities whose
+/// [`BlendMode`] has changed with the one using the new blend mode, and
+/// restores the default one on the entities that lost their blend mode.
+pub(crate) fn apply_blend_modes(
+    changed: Query<Entity, Changed<BlendMode>>,
+    removed: RemovedComponents<BlendMode>,
+    mut query: Query<(Option<&BlendMode>, &mut RenderPipelines)>,
+) {
+    let shape_pipelines = [
+        SHAPE_PIPELINE_HANDLE.id,
+        ADDITIVE_SHAPE_PIPELINE_HANDLE.id,
+        MULTIPLY_SHAPE_PIPELINE_HANDLE.id,
+    ];
+    for entity in changed.iter().chain(removed.iter()) {
+        if let Ok((mode, mut render_pipelines)) = query.get_mut(entity) {
+            let pipeline = mode.copied().unwrap_or_default().pipeline();
+            for render_pipeline in &mut render_pipelines.pipelines {
+                if shape_pipelines.contains(&render_pipeline.pipeline.id) {
+                    render_pipeline.pipeline = pipeline.clone().typed();
+                }
+            }
+        }
+    }
+}
+
+/// A bevy system. Adds the wireframe pipeline to the entities that got a
+/// [`ShapeWireframe`], and removes it from the ones that lost it.
+pub(crate) fn update_wireframes(
+    added: Query<Entity, Added<ShapeWireframe>>,
+    removed: RemovedComponents<ShapeWireframe>,
+    mut render_pipelines: Query<&mut RenderPipelines>,
+) {
+    for entity in added.iter() {
+        if let Ok(mut render_pipelines) = render_pipelines.get_mut(entity) {
+            render_pipelines
+                .pipelines
+                .push(RenderPipeline::new(WIREFRAME_SHAPE_PIPELINE_HANDLE.typed()));
+        }
+    }
+
+    for entity in removed.iter() {
+        if let Ok(mut render_pipelines) = render_pipelines.get_mut(entity) {
+            render_pipelines
+                .pipelines
+                .retain(|pipeline| pipeline.pipeline.id != WIREFRAME_SHAPE_PIPELINE_HANDLE.id);
+        }
+    }
 }
@@ -0,0 +1,62 @@
+#version 450
+
+layout(location = 0) in vec4 v_color;
+layout(location = 1) in vec4 v_outline_color;
+layout(location = 2) in vec2 v_position;
+// x: kind of primitive, y and z: its parameters, w: outline width.
+layout(location = 3) in vec4 v_shape;
+// Corner radii of rounded rectangles: top-right, bottom-right, top-left,
+// bottom-left.
+layout(location = 4) in vec4 v_radii;
+
+layout(location = 0) out vec4 o_Target;
+
+float sd_rounded_rectangle(vec2 p, vec2 half_size, vec4 r) {
+    r.xy = (p.x > 0.0) ? r.xy : r.zw;
+    r.x = (p.y > 0.0) ? r.x : r.y;
+    vec2 q = abs(p) - half_size + r.x;
+    return min(max(q.x, q.y), 0.0) + length(max(q, 0.0)) - r.x;
+}
+
+float distance_to_shape(vec2 p) {
+    int kind = int(v_shape.x + 0.5);
+    if (kind == 0) {
+        // Circle: radius.
+        return length(p) - v_shape.y;
+    } else if (kind == 1) {
+        // Ring: outer radius, inner radius.
+        float mid = (v_shape.y + v_shape.z) * 0.5;
+        return abs(length(p) - mid) - (v_shape.y - v_shape.z) * 0.5;
+    } else if (kind == 2) {
+        // Rounded rectangle: half width, half height.
+        return sd_rounded_rectangle(p, v_shape.yz, v_radii);
+    } else {
+        // Capsule aligned to the x axis: half length, radius.
+        vec2 q = vec2(max(abs(p.x) - v_shape.y, 0.0), p.y);
+        return length(q) - v_shape.z;
+    }
+}
+
+void main() {
+    float d = distance_to_shape(v_position);
+    // Width of a pixel in the units of the distance field.
+    float aa = max(fwidth(d), 0.0001);
+
+    float fill_coverage = clamp(0.5 - d / aa, 0.0, 1.0);
+    float outline_width = v_shape.w;
+    float outline_coverage = outline_width > 0.0
+        ? clamp(0.5 - (abs(d) - outline_width * 0.5) / aa, 0.0, 1.0)
+        : 0.0;
+
+    // The outline is drawn over the fill.
+    float fill_alpha = v_color.a * fill_coverage;
+    float outline_alpha = v_outline_color.a * outline_coverage;
+    float alpha = outline_alpha + fill_alpha * (1.0 - outline_alpha);
+    if (alpha <= 0.0) {
+        discard;
+    }
+    vec3 color = (v_outline_color.rgb * outline_alpha
+        + v_color.rgb * fill_alpha * (1.0 - outline_alpha)) / alpha;
+
+    o_Target = vec4(color, alpha);
+}
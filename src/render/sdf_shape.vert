@@ -0,0 +1,33 @@
+#version 450
+
+layout(location = 0) in vec2 Vertex_Position_2D;
+layout(location = 1) in vec4 Vertex_Color;
+layout(location = 2) in vec4 Vertex_Sdf_Outline_Color;
+layout(location = 3) in vec2 Vertex_Sdf_Position;
+layout(location = 4) in vec4 Vertex_Sdf_Shape;
+layout(location = 5) in vec4 Vertex_Sdf_Radii;
+
+layout(location = 0) out vec4 v_color;
+layout(location = 1) out vec4 v_outline_color;
+layout(location = 2) out vec2 v_position;
+layout(location = 3) out vec4 v_shape;
+layout(location = 4) out vec4 v_radii;
+
+layout(set = 0, binding = 0) uniform CameraViewProj {
+    mat4 ViewProj;
+};
+layout(set = 1, binding = 0) uniform Transform {
+    mat4 Model;
+};
+layout(set = 1, binding = 1) uniform GlobalShapeAlpha_value {
+    float ShapeAlpha;
+};
+
+void main() {
+    v_color = vec4(Vertex_Color.rgb, Vertex_Color.a * ShapeAlpha);
+    v_outline_color = vec4(Vertex_Sdf_Outline_Color.rgb, Vertex_Sdf_Outline_Color.a * ShapeAlpha);
+    v_position = Vertex_Sdf_Position;
+    v_shape = Vertex_Sdf_Shape;
+    v_radii = Vertex_Sdf_Radii;
+    gl_Position = ViewProj * Model * vec4(Vertex_Position_2D, 0.0, 1.0);
+}
@@ -11,8 +11,11 @@ layout(set = 0, binding = 0) uniform CameraViewProj {
 layout(set = 1, binding = 0) uniform Transform {
     mat4 Model;
 };
+layout(set = 1, binding = 1) uniform GlobalShapeAlpha_value {
+    float ShapeAlpha;
+};
 
 void main() {
-    v_color = Vertex_Color;
+    v_color = vec4(Vertex_Color.rgb, Vertex_Color.a * ShapeAlpha);
     gl_Position = ViewProj * Model * vec4(Vertex_Position_2D, 0.0, 1.0);
 }
\ No newline at end of file
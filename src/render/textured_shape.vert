@@ -0,0 +1,24 @@
+#version 450
+
+layout(location = 0) in vec2 Vertex_Position_2D;
+layout(location = 1) in vec4 Vertex_Color;
+layout(location = 2) in vec2 Vertex_Uv;
+
+layout(location = 0) out vec4 v_color;
+layout(location = 1) out vec2 v_uv;
+
+layout(set = 0, binding = 0) uniform CameraViewProj {
+    mat4 ViewProj;
+};
+layout(set = 1, binding = 0) uniform Transform {
+    mat4 Model;
+};
+layout(set = 1, binding = 1) uniform GlobalShapeAlpha_value {
+    float ShapeAlpha;
+};
+
+void main() {
+    v_color = vec4(Vertex_Color.rgb, Vertex_Color.a * ShapeAlpha);
+    v_uv = Vertex_Uv;
+    gl_Position = ViewProj * Model * vec4(Vertex_Position_2D, 0.0, 1.0);
+}
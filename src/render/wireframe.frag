@@ -0,0 +1,9 @@
+#version 450
+
+layout(location = 0) in vec4 v_color;
+
+layout(location = 0) out vec4 o_Target;
+
+void main() {
+    o_Target = vec4(1.0 - v_color.rgb, 1.0);
+}
@@ -0,0 +1,301 @@
+//! Shapes drawn with signed distance fields.
+//!
+//! Some primitives have a simple analytic distance function, so they can be
+//! drawn by evaluating it in the fragment shader on a quad, instead of being
+//! tessellated. Their edges stay perfectly smooth and antialiased at any zoom
+//! level, without lowering the tessellation tolerance.
+//!
+//! Spawn an [`SdfShapeBundle`] to draw one of the [`SdfPrimitive`]s. The
+//! [`ShapePlugin`](crate::plugin::ShapePlugin) creates its quad in the
+//! [`Stage::Shape`](crate::plugin::Stage::Shape) stage.
+
+use bevy::{
+    asset::{Assets, Handle},
+    ecs::{
+        bundle::Bundle,
+        query::{Changed, Or},
+        system::{Query, ResMut},
+    },
+    math::Vec2,
+    render::{
+        color::Color,
+        draw::{Draw, Visible},
+        mesh::{Indices, Mesh},
+        pipeline::{PrimitiveTopology, RenderPipeline, RenderPipelines},
+        render_graph::base::MainPass,
+    },
+    sprite::QUAD_HANDLE,
+    transform::components::{GlobalTransform, Transform},
+};
+use lyon_tessellation::FillOptions;
+
+use crate::{
+    entity::{GlobalShapeAlpha, ShapeColors},
+    plugin::ATTRIBUTE_POSITION_2D,
+    render::SDF_SHAPE_PIPELINE_HANDLE,
+    shapes,
+    utils::{Convert, DrawMode},
+};
+
+/// Distance added around the quad of a primitive, so its antialiased edge is
+/// not clipped.
+const EDGE_MARGIN: f32 = 2.0;
+
+/// A primitive that can be drawn with a signed distance field. It uses the
+/// same parameters as the corresponding tessellated shape.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SdfPrimitive {
+    Circle(shapes::Circle),
+    Ring(shapes::Ring),
+    RoundedRectangle(shapes::RoundedRectangle),
+    Capsule(shapes::Capsule),
+}
+
+/// The parameters of a primitive, in the frame where it is centered and
+/// axis-aligned.
+struct SdfFrame {
+    center: Vec2,
+    direction: Vec2,
+    half_extents: Vec2,
+    shape: [f32; 3],
+    radii: [f32; 4],
+}
+
+impl SdfFrame {
+    fn axis_aligned(center: Vec2, half_extents: Vec2, shape: [f32; 3]) -> Self {
+        Self {
+            center,
+            direction: Vec2::new(1.0, 0.0),
+            half_extents,
+            shape,
+            radii: [0.0; 4],
+        }
+    }
+}
+
+impl SdfPrimitive {
+    fn frame(&self) -> SdfFrame {
+        match *self {
+            Self::Circle(circle) => SdfFrame::axis_aligned(
+                circle.center,
+                Vec2::splat(circle.radius),
+                [0.0, circle.radius, 0.0],
+            ),
+            Self::Ring(ring) => SdfFrame::axis_aligned(
+                ring.center,
+                Vec2::splat(ring.outer_radius),
+                [1.0, ring.outer_radius, ring.inner_radius],
+            ),
+            Self::RoundedRectangle(rectangle) => {
+                let half_size = Vec2::new(rectangle.width, rectangle.height) / 2.0;
+                // Unlike `clamp`, this doesn't panic for a negative size.
+                let radius = |value: f32| value.max(0.0).min(half_size.abs().min_element());
+                let bottom_left: Vec2 = rectangle
+                    .origin
+                    .bottom_left(rectangle.width, rectangle.height)
+                    .convert();
+
+                SdfFrame {
+                    radii: [
+                        radius(rectangle.radii.top_right),
+                        radius(rectangle.radii.bottom_right),
+                        radius(rectangle.radii.top_left),
+                        radius(rectangle.radii.bottom_left),
+                    ],
+                    ..SdfFrame::axis_aligned(
+                        bottom_left + half_size,
+                        half_size,
+                        [2.0, half_size.x, half_size.y],
+                    )
+                }
+            }
+            Self::Capsule(capsule) => {
+                let axis = capsule.end - capsule.start;
+                let half_length = axis.length() / 2.0;
+
+                SdfFrame {
+                    direction: if half_length > 0.0 {
+                        axis.normalize()
+                    } else {
+                        Vec2::new(1.0, 0.0)
+                    },
+                    ..SdfFrame::axis_aligned(
+                        (capsule.start + capsule.end) / 2.0,
+                        Vec2::new(half_length + capsule.radius, capsule.radius),
+                        [3.0, half_length, capsule.radius],
+                    )
+                }
+            }
+        }
+    }
+}
+
+/// A Bevy `Bundle` to represent a primitive drawn with a signed distance
+/// field.
+///
+/// The [`DrawMode`] is used only to choose between fill, stroke and outline,
+/// and for the width of the line: the other fill and stroke options have no
+/// effect.
+///
+/// # Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_prototype_lyon::{
+///     prelude::*,
+///     sdf::{SdfPrimitive, SdfShapeBundle},
+/// };
+///
+/// fn some_system(mut commands: Commands) {
+///     let circle = shapes::Circle {
+///         radius: 100.0,
+///         ..shapes::Circle::default()
+///     };
+///     commands.spawn_bundle(SdfShapeBundle::new(
+///         SdfPrimitive::Circle(circle),
+///         ShapeColors::outlined(Color::TEAL, Color::BLACK),
+///         DrawMode::Outlined {
+///             fill_options: FillOptions::default(),
+///             outline_options: StrokeOptions::default().with_line_width(5.0),
+///         },
+///         Transform::default(),
+///     ));
+/// }
+/// ```
+#[allow(missing_docs)]
+#[derive(Bundle)]
+pub struct SdfShapeBundle {
+    pub primitive: SdfPrimitive,
+    pub mode: DrawMode,
+    pub mesh: Handle<Mesh>,
+    pub colors: ShapeColors,
+    pub alpha: GlobalShapeAlpha,
+    pub main_pass: MainPass,
+    pub draw: Draw,
+    pub visible: Visible,
+    pub render_pipelines: RenderPipelines,
+    pub transform: Transform,
+    pub global_transform: GlobalTransform,
+}
+
+impl SdfShapeBundle {
+    /// Creates an `SdfShapeBundle` drawing the given primitive.
+    #[must_use]
+    pub fn new(
+        primitive: SdfPrimitive,
+        colors: ShapeColors,
+        mode: DrawMode,
+        transform: Transform,
+    ) -> Self {
+        Self {
+            primitive,
+            colors,
+            mode,
+            transform,
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for SdfShapeBundle {
+    fn default() -> Self {
+        Self {
+            primitive: SdfPrimitive::Circle(shapes::Circle::default()),
+            mode: DrawMode::Fill(FillOptions::default()),
+            mesh: QUAD_HANDLE.typed(),
+            render_pipelines: RenderPipelines::from_pipelines(vec![RenderPipeline::new(
+                SDF_SHAPE_PIPELINE_HANDLE.typed(),
+            )]),
+            visible: Visible {
+                is_visible: false,
+                is_transparent: true,
+            },
+            main_pass: MainPass,
+            draw: Draw::default(),
+            colors: ShapeColors {
+                main: Color::WHITE,
+                outline: Color::BLACK,
+            },
+            alpha: GlobalShapeAlpha::default(),
+            transform: Transform::default(),
+            global_transform: GlobalTransform::default(),
+        }
+    }
+}
+
+/// A bevy system. Queries all the [`SdfShapeBundle`]s to complete them with a
+/// quad mesh. The quad is built again when the primitive, the draw mode or
+/// the colors change, which is cheap since it only has four vertices.
+pub(crate) fn complete_sdf_bundle(
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut query: Query<
+        (
+            &SdfPrimitive,
+            &DrawMode,
+            &ShapeColors,
+            &mut Handle<Mesh>,
+            &mut Visible,
+        ),
+        Or<(
+            Changed<SdfPrimitive>,
+            Changed<DrawMode>,
+            Changed<ShapeColors>,
+        )>,
+    >,
+) {
+    for (primitive, mode, colors, mut mesh, mut visible) in query.iter_mut() {
+        *mesh = meshes.add(build_sdf_mesh(primitive, mode, colors));
+        visible.is_visible = true;
+    }
+}
+
+fn build_sdf_mesh(primitive: &SdfPrimitive, mode: &DrawMode, colors: &ShapeColors) -> Mesh {
+    let (fill, outline, line_width) = match mode {
+        DrawMode::Fill(_) => (colors.main, Color::NONE, 0.0),
+        DrawMode::Stroke(options) => (Color::NONE, colors.main, options.line_width),
+        DrawMode::Outlined {
+            outline_options, ..
+        } => (colors.main, colors.outline, outline_options.line_width),
+    };
+    let frame = primitive.frame();
+    let extents = frame.half_extents + Vec2::splat(line_width / 2.0 + EDGE_MARGIN);
+    let normal = Vec2::new(-frame.direction.y, frame.direction.x);
+    let corners = [
+        Vec2::new(-extents.x, -extents.y),
+        Vec2::new(extents.x, -extents.y),
+        Vec2::new(extents.x, extents.y),
+        Vec2::new(-extents.x, extents.y),
+    ];
+    let rgba = |color: Color| [color.r(), color.g(), color.b(), color.a()];
+
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+    mesh.set_indices(Some(Indices::U32(vec![0, 1, 2, 0, 2, 3])));
+    mesh.set_attribute(
+        ATTRIBUTE_POSITION_2D,
+        corners
+            .iter()
+            .map(|c| {
+                let p = frame.center + frame.direction * c.x + normal * c.y;
+                [p.x, p.y]
+            })
+            .collect::<Vec<[f32; 2]>>(),
+    );
+    mesh.set_attribute(Mesh::ATTRIBUTE_COLOR, vec![rgba(fill); 4]);
+    mesh.set_attribute("Vertex_Sdf_Outline_Color", vec![rgba(outline); 4]);
+    mesh.set_attribute(
+        "Vertex_Sdf_Position",
+        corners
+            .iter()
+            .map(|c| [c.x, c.y])
+            .collect::<Vec<[f32; 2]>>(),
+    );
+    let [kind, first, second] = frame.shape;
+    mesh.set_attribute(
+        "Vertex_Sdf_Shape",
+        vec![[kind, first, second, line_width]; 4],
+    );
+    mesh.set_attribute("Vertex_Sdf_Radii", vec![frame.radii; 4]);
+
+    mesh
+}
@@ -4,8 +4,9 @@
 //! [`Geometry`](crate::geometry::Geometry) trait. You can also implement
 //! the trait for your own shapes.
 
-use bevy::math::Vec2;
+use bevy::{math::Vec2, sprite::Rect as BevyRect};
 use lyon_tessellation::{
+    geom,
     math::{point, Angle, Point, Rect, Size, Vector},
     path::{
         builder::WithSvg,
@@ -16,7 +17,147 @@ use lyon_tessellation::{
 };
 use svgtypes::{Path, PathSegment};
 
-use crate::{geometry::Geometry, utils::Convert};
+use crate::{
+    geometry::Geometry,
+    utils::{Convert, SplitMix64},
+};
+
+/// Returns the signed area of a polygon, computed with the shoelace formula.
+/// The result is positive if the vertices are in counterclockwise order.
+fn signed_area(points: &[Vec2]) -> f32 {
+    let mut area = 0.0;
+    for (i, p) in points.iter().enumerate() {
+        let q = points[(i + 1) % points.len()];
+        area += p.x.mul_add(q.y, -q.x * p.y);
+    }
+
+    area / 2.0
+}
+
+/// Adds a sub-path passing through all the given points, using a cardinal
+/// spline converted to cubic bézier curves. A `tension` of `0.0` gives a
+/// Catmull-Rom spline, while `1.0` gives straight lines.
+#[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)] // indices are bounded by `n`
+fn add_cardinal_spline(b: &mut Builder, points: &[Vec2], tension: f32, closed: bool) {
+    let n = points.len();
+    if n < 2 {
+        return;
+    }
+
+    let get = |i: isize| {
+        if closed {
+            points[i.rem_euclid(n as isize) as usize]
+        } else {
+            points[i.clamp(0, n as isize - 1) as usize]
+        }
+    };
+    let tangent = |i: isize| (get(i + 1) - get(i - 1)) * (1.0 - tension) / 2.0;
+
+    let segments = if closed { n } else { n - 1 };
+    b.begin(points[0].convert());
+    for i in 0..segments as isize {
+        b.cubic_bezier_to(
+            (get(i) + tangent(i) / 3.0).convert(),
+            (get(i + 1) - tangent(i + 1) / 3.0).convert(),
+            get(i + 1).convert(),
+        );
+    }
+    b.end(closed);
+}
+
+/// Adds a sub-path passing through the given points, replacing every corner
+/// with a circular arc of the given radius. The radius is reduced where the
+/// edges are too short to fit it. The first and last points of open sub-paths
+/// are kept sharp.
+pub(crate) fn add_rounded_polyline(b: &mut Builder, points: &[Vec2], radius: f32, closed: bool) {
+    let n = points.len();
+    if n < 2 {
+        return;
+    }
+
+    for (i, &corner) in points.iter().enumerate() {
+        let to_prev = points[(i + n - 1) % n] - corner;
+        let to_next = points[(i + 1) % n] - corner;
+        let is_endpoint = !closed && (i == 0 || i == n - 1);
+
+        // -- Implementation details **PLEASE KEEP UPDATED** --
+        // - `half_angle`: half of the angle between the two edges.
+        // - `tangent`: distance between the corner and the points where the arc touches
+        //   the edges.
+        // - `bisector`: direction from the corner to the center of the arc.
+        let (from, arc) =
+            if is_endpoint || to_prev.length_squared() == 0.0 || to_next.length_squared() == 0.0 {
+                (corner, None)
+            } else {
+                let (u, v) = (to_prev.normalize(), to_next.normalize());
+                let half_angle = u.dot(v).clamp(-1.0, 1.0).acos() / 2.0;
+                let max_tangent = to_prev.length().min(to_next.length()) / 2.0;
+                let tangent = (radius / half_angle.tan()).min(max_tangent);
+                let radius = tangent * half_angle.tan();
+                let bisector = u + v;
+
+                if radius <= f32::EPSILON || bisector.length_squared() <= f32::EPSILON {
+                    (corner, None)
+                } else {
+                    let (from, to) = (corner + u * tangent, corner + v * tangent);
+                    let center = corner + bisector.normalize() * (radius / half_angle.sin());
+                    let (start, end) = (from - center, to - center);
+                    let sweep = 2.0f32.mul_add(-half_angle, std::f32::consts::PI);
+                    let sweep = if start.x.mul_add(end.y, -start.y * end.x) < 0.0 {
+                        -sweep
+                    } else {
+                        sweep
+                    };
+                    (from, Some((center, radius, start.y.atan2(start.x), sweep)))
+                }
+            };
+
+        if i == 0 {
+            b.begin(from.convert());
+        } else {
+            b.line_to(from.convert());
+        }
+        if let Some((center, radius, start_angle, sweep_angle)) = arc {
+            arc_to(b, center.convert(), radius, start_angle, sweep_angle);
+        }
+    }
+    b.end(closed);
+}
+
+/// Adds a circular arc to the current sub-path of the builder, approximated
+/// with quadratic bézier curves. The current position of the builder must be
+/// the starting point of the arc. Angles are in radians, counterclockwise.
+fn arc_to(b: &mut Builder, center: Point, radius: f32, start_angle: f32, sweep_angle: f32) {
+    if radius <= 0.0 || sweep_angle.abs() <= f32::EPSILON {
+        return;
+    }
+
+    let arc = geom::Arc {
+        center,
+        radii: Vector::new(radius, radius),
+        start_angle: Angle::radians(start_angle),
+        sweep_angle: Angle::radians(sweep_angle),
+        x_rotation: Angle::zero(),
+    };
+    arc.for_each_quadratic_bezier(&mut |s| {
+        b.quadratic_bezier_to(s.ctrl, s.to);
+    });
+}
+
+/// Pushes a point to a half of a convex hull, removing the points that would
+/// make it concave. The first `min_len - 1` points are never removed.
+fn push_hull_point(hull: &mut Vec<Vec2>, min_len: usize, point: Vec2) {
+    while hull.len() >= min_len {
+        let last = hull[hull.len() - 1];
+        let previous = hull[hull.len() - 2];
+        let (edge, next) = (last - previous, point - previous);
+        if edge.x.mul_add(next.y, -edge.y * next.x) > 0.0 {
+            break;
+        }
+        hull.pop();
+    }
+    hull.push(point);
+}
 
 /// Defines where the origin, or pivot of the `Rectangle` should be positioned.
 #[allow(missing_docs)]
@@ -30,6 +171,21 @@ pub enum RectangleOrigin {
     CustomCenter(Vec2),
 }
 
+impl RectangleOrigin {
+    /// Returns the position of the bottom-left corner of a rectangle with the
+    /// given size, relative to this origin.
+    pub(crate) fn bottom_left(self, width: f32, height: f32) -> Point {
+        match self {
+            Self::Center => Point::new(-width / 2.0, -height / 2.0),
+            Self::BottomLeft => Point::new(0.0, 0.0),
+            Self::BottomRight => Point::new(-width, 0.0),
+            Self::TopRight => Point::new(-width, -height),
+            Self::TopLeft => Point::new(0.0, -height),
+            Self::CustomCenter(v) => Point::new(v.x - width / 2.0, v.y - height / 2.0),
+        }
+    }
+}
+
 impl Default for RectangleOrigin {
     fn default() -> Self {
         Self::Center
@@ -54,18 +210,21 @@ impl Default for Rectangle {
     }
 }
 
+/// Converts a Bevy rectangle into a `Rectangle` with the same position, using
+/// a [`RectangleOrigin::CustomCenter`].
+impl From<BevyRect> for Rectangle {
+    fn from(rect: BevyRect) -> Self {
+        Self {
+            width: rect.width(),
+            height: rect.height(),
+            origin: RectangleOrigin::CustomCenter((rect.min + rect.max) / 2.0),
+        }
+    }
+}
+
 impl Geometry for Rectangle {
     fn add_geometry(&self, b: &mut Builder) {
-        let origin = match self.origin {
-            RectangleOrigin::Center => Point::new(-self.width / 2.0, -self.height / 2.0),
-            RectangleOrigin::BottomLeft => Point::new(0.0, 0.0),
-            RectangleOrigin::BottomRight => Point::new(-self.width, 0.0),
-            RectangleOrigin::TopRight => Point::new(-self.width, -self.height),
-            RectangleOrigin::TopLeft => Point::new(0.0, -self.height),
-            RectangleOrigin::CustomCenter(v) => {
-                Point::new(v.x - self.width / 2.0, v.y - self.height / 2.0)
-            }
-        };
+        let origin = self.origin.bottom_left(self.width, self.height);
 
         b.add_rectangle(
             &Rect::new(origin, Size::new(self.width, self.height)),
@@ -74,6 +233,12 @@ impl Geometry for Rectangle {
     }
 }
 
+/// A circle, specified by its radius and center.
+///
+/// The circle is built out of curves rather than straight segments, so its
+/// smoothness is controlled by the `tolerance` field of the options passed in
+/// the [`DrawMode`](crate::utils::DrawMode): lower values give smoother
+/// results when the shape is zoomed in.
 #[allow(missing_docs)]
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Circle {
@@ -96,11 +261,14 @@ impl Geometry for Circle {
     }
 }
 
+/// An ellipse, specified by its radii along the x and y axes.
 #[allow(missing_docs)]
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Ellipse {
     pub radii: Vec2,
     pub center: Vec2,
+    /// The rotation of the ellipse axes, in radians.
+    pub rotation: f32,
 }
 
 impl Default for Ellipse {
@@ -108,6 +276,7 @@ impl Default for Ellipse {
         Self {
             radii: Vec2::ONE,
             center: Vec2::ZERO,
+            rotation: 0.0,
         }
     }
 }
@@ -117,17 +286,27 @@ impl Geometry for Ellipse {
         b.add_ellipse(
             self.center.convert(),
             self.radii.convert(),
-            Angle::zero(),
+            Angle::radians(self.rotation),
             Winding::Positive,
         );
     }
 }
 
+/// A polygon, or a polyline, defined by its vertices.
+///
+/// When `closed` is `false`, the last point is not connected back to the
+/// first one, so the shape is an open polyline that is best suited to be
+/// stroked.
+///
+/// A closed polygon can also have holes. Every hole is added as a separate
+/// sub-path wound in the opposite direction of `points`, so the holes are
+/// left empty regardless of the fill rule.
 #[allow(missing_docs)]
 #[derive(Debug, Clone, PartialEq)]
 pub struct Polygon {
     pub points: Vec<Vec2>,
     pub closed: bool,
+    pub holes: Vec<Vec<Vec2>>,
 }
 
 impl Default for Polygon {
@@ -135,6 +314,29 @@ impl Default for Polygon {
         Self {
             points: Vec::new(),
             closed: true,
+            holes: Vec::new(),
+        }
+    }
+}
+
+/// Creates a closed polygon without holes. Set `closed` to `false` to get a
+/// polyline:
+///
+/// ```
+/// use bevy::math::Vec2;
+/// use bevy_prototype_lyon::shapes::Polygon;
+///
+/// let points = [Vec2::ZERO, Vec2::new(10.0, 0.0), Vec2::new(10.0, 10.0)];
+/// let polyline = Polygon {
+///     closed: false,
+///     ..Polygon::from(&points[..])
+/// };
+/// ```
+impl From<&[Vec2]> for Polygon {
+    fn from(points: &[Vec2]) -> Self {
+        Self {
+            points: points.to_vec(),
+            ..Self::default()
         }
     }
 }
@@ -152,6 +354,19 @@ impl Geometry for Polygon {
         };
 
         b.add_polygon(polygon);
+
+        let exterior_area = signed_area(&self.points);
+        for hole in &self.holes {
+            let mut points = hole.iter().map(|p| p.convert()).collect::<Vec<Point>>();
+            if signed_area(hole) * exterior_area > 0.0 {
+                points.reverse();
+            }
+
+            b.add_polygon(LyonPolygon {
+                points: points.as_slice(),
+                closed: true,
+            });
+        }
     }
 }
 
@@ -167,12 +382,17 @@ pub enum RegularPolygonFeature {
     SideLength(f32),
 }
 
+/// A polygon with all sides and internal angles equal.
 #[allow(missing_docs)]
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct RegularPolygon {
     pub sides: usize,
     pub center: Vec2,
     pub feature: RegularPolygonFeature,
+    /// Counterclockwise rotation of the polygon, in radians. With a value of
+    /// zero, the polygon lies flat on a side parallel to the x-axis (e.g. a
+    /// flat-top hexagon). Use `PI / sides` to get the pointy-top variant.
+    pub rotation: f32,
 }
 
 impl RegularPolygon {
@@ -194,6 +414,7 @@ impl Default for RegularPolygon {
             sides: 3,
             center: Vec2::ZERO,
             feature: RegularPolygonFeature::Radius(1.0),
+            rotation: 0.0,
         }
     }
 }
@@ -203,14 +424,15 @@ impl Geometry for RegularPolygon {
         // -- Implementation details **PLEASE KEEP UPDATED** --
         // - `step`: angle between two vertices.
         // - `internal`: internal angle of the polygon.
-        // - `offset`: bias to make the shape lay flat on a line parallel to the x-axis.
+        // - `offset`: bias to make the shape lay flat on a line parallel to the x-axis,
+        //   plus the user-defined rotation.
 
         use std::f32::consts::PI;
         assert!(self.sides > 2, "Polygons must have at least 3 sides");
         let n = self.sides as f32;
         let radius = self.radius();
         let internal = (n - 2.0) * PI / n;
-        let offset = -internal / 2.0;
+        let offset = -internal / 2.0 + self.rotation;
 
         let mut points = Vec::with_capacity(self.sides);
         let step = 2.0 * PI / n;
@@ -231,6 +453,12 @@ impl Geometry for RegularPolygon {
 }
 
 /// A simple line segment, specified by two points.
+///
+/// A line has no area, so it should be drawn with [`DrawMode::Stroke`]: the
+/// width and the caps of the line are controlled by the [`StrokeOptions`].
+///
+/// [`DrawMode::Stroke`]: crate::utils::DrawMode::Stroke
+/// [`StrokeOptions`]: lyon_tessellation::StrokeOptions
 #[allow(missing_docs)]
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Line(pub Vec2, pub Vec2);
@@ -243,25 +471,1853 @@ impl Geometry for Line {
         });
     }
 }
-///An easy way to display svg paths as a shape, takes an svg path string and a
-///document size(Vec2).
-///
-///For documentation on svg paths: <https://developer.mozilla.org/en-US/docs/Web/SVG/Tutorial/Paths>
-///
-///Make sure that your units are pixels(px) and that the transform of the \<g\>
-///in your svg document is set to transform="translate(0,0)" so as to not
-///offset the coordinates of the paths
-///
-///In inkscape for example, to turn your units into pixels, you:
-///1) Go to File>Document Properties>General>Display Units and set it to px
-///
-///2) In File>Document Properties>Custom Size>Units set it to px, also, this
-/// size would be used for `svg_doc_size_in_px`
+
+/// A star polygon, with its points alternating between an outer and an inner
+/// radius. The first point faces upwards.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Star {
+    pub points: usize,
+    pub inner_radius: f32,
+    pub outer_radius: f32,
+    pub center: Vec2,
+}
+
+impl Default for Star {
+    fn default() -> Self {
+        Self {
+            points: 5,
+            inner_radius: 0.5,
+            outer_radius: 1.0,
+            center: Vec2::ZERO,
+        }
+    }
+}
+
+impl Geometry for Star {
+    fn add_geometry(&self, b: &mut Builder) {
+        use std::f32::consts::{FRAC_PI_2, PI};
+        assert!(self.points > 1, "Stars must have at least 2 points");
+        let step = PI / self.points as f32;
+
+        let points = (0..self.points * 2)
+            .map(|i| {
+                let radius = if i % 2 == 0 {
+                    self.outer_radius
+                } else {
+                    self.inner_radius
+                };
+                let angle = (i as f32).mul_add(step, FRAC_PI_2);
+                point(
+                    radius.mul_add(angle.cos(), self.center.x),
+                    radius.mul_add(angle.sin(), self.center.y),
+                )
+            })
+            .collect::<Vec<Point>>();
+
+        b.add_polygon(LyonPolygon {
+            points: points.as_slice(),
+            closed: true,
+        });
+    }
+}
+
+/// The radii of the four corners of a [`RoundedRectangle`].
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CornerRadii {
+    pub top_left: f32,
+    pub top_right: f32,
+    pub bottom_right: f32,
+    pub bottom_left: f32,
+}
+
+impl CornerRadii {
+    /// Creates a `CornerRadii` with the same radius on every corner.
+    #[must_use]
+    pub const fn uniform(radius: f32) -> Self {
+        Self {
+            top_left: radius,
+            top_right: radius,
+            bottom_right: radius,
+            bottom_left: radius,
+        }
+    }
+}
+
+impl Default for CornerRadii {
+    fn default() -> Self {
+        Self::uniform(0.0)
+    }
+}
+
+/// A rectangle with rounded corners. Each corner can have a different radius.
+/// Radii are clamped to half of the shortest side, and corners with a zero
+/// radius are left sharp.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RoundedRectangle {
+    pub width: f32,
+    pub height: f32,
+    pub origin: RectangleOrigin,
+    pub radii: CornerRadii,
+}
+
+impl Default for RoundedRectangle {
+    fn default() -> Self {
+        Self {
+            width: 1.0,
+            height: 1.0,
+            origin: RectangleOrigin::default(),
+            radii: CornerRadii::uniform(0.1),
+        }
+    }
+}
+
+impl Geometry for RoundedRectangle {
+    fn add_geometry(&self, b: &mut Builder) {
+        use std::f32::consts::{FRAC_PI_2, PI};
+
+        let origin = self.origin.bottom_left(self.width, self.height);
+        let (x, y) = (origin.x, origin.y);
+        let (width, height) = (self.width, self.height);
+        // Unlike `clamp`, this doesn't panic for a negative or NaN size.
+        let max_radius = (width.min(height) / 2.0).max(0.0);
+        let tl = self.radii.top_left.max(0.0).min(max_radius);
+        let tr = self.radii.top_right.max(0.0).min(max_radius);
+        let br = self.radii.bottom_right.max(0.0).min(max_radius);
+        let bl = self.radii.bottom_left.max(0.0).min(max_radius);
+
+        b.begin(point(x + bl, y));
+        b.line_to(point(x + width - br, y));
+        arc_to(b, point(x + width - br, y + br), br, -FRAC_PI_2, FRAC_PI_2);
+        b.line_to(point(x + width, y + height - tr));
+        arc_to(
+            b,
+            point(x + width - tr, y + height - tr),
+            tr,
+            0.0,
+            FRAC_PI_2,
+        );
+        b.line_to(point(x + tl, y + height));
+        arc_to(b, point(x + tl, y + height - tl), tl, FRAC_PI_2, FRAC_PI_2);
+        b.line_to(point(x, y + bl));
+        arc_to(b, point(x + bl, y + bl), bl, PI, FRAC_PI_2);
+        b.close();
+    }
+}
+
+/// An annulus: a circle with a concentric circular hole.
 ///
-///3) In File>Document Properties>Scale>Scale x make sure it is set to 1 User
-/// unit per px
+/// The hole is wound in the opposite direction of the outer circle, so it is
+/// left empty by both the non-zero and the even-odd fill rules.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ring {
+    pub inner_radius: f32,
+    pub outer_radius: f32,
+    pub center: Vec2,
+}
+
+impl Default for Ring {
+    fn default() -> Self {
+        Self {
+            inner_radius: 0.5,
+            outer_radius: 1.0,
+            center: Vec2::ZERO,
+        }
+    }
+}
+
+impl Geometry for Ring {
+    fn add_geometry(&self, b: &mut Builder) {
+        let center = self.center.convert();
+        b.add_circle(center, self.outer_radius, Winding::Positive);
+        b.add_circle(center, self.inner_radius, Winding::Negative);
+    }
+}
+
+/// A capsule (or stadium): a rectangle with semicircular ends, defined by the
+/// centers of the two ends and the radius.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Capsule {
+    pub start: Vec2,
+    pub end: Vec2,
+    pub radius: f32,
+}
+
+impl Default for Capsule {
+    fn default() -> Self {
+        Self {
+            start: Vec2::new(-0.5, 0.0),
+            end: Vec2::new(0.5, 0.0),
+            radius: 0.5,
+        }
+    }
+}
+
+impl Geometry for Capsule {
+    fn add_geometry(&self, b: &mut Builder) {
+        use std::f32::consts::{FRAC_PI_2, PI};
+
+        let axis = self.end - self.start;
+        if axis.length_squared() <= f32::EPSILON {
+            b.add_circle(self.start.convert(), self.radius, Winding::Positive);
+            return;
+        }
+
+        let angle = axis.y.atan2(axis.x);
+        let normal = Vec2::new(-axis.y, axis.x).normalize() * self.radius;
+
+        b.begin((self.start - normal).convert());
+        b.line_to((self.end - normal).convert());
+        arc_to(b, self.end.convert(), self.radius, angle - FRAC_PI_2, PI);
+        b.line_to((self.start + normal).convert());
+        arc_to(b, self.start.convert(), self.radius, angle + FRAC_PI_2, PI);
+        b.close();
+    }
+}
+
+/// A circular arc. Angles are in radians, counterclockwise from the positive
+/// x-axis.
 ///
-///Example exists in the examples folder
+/// By default the arc is an open path, best suited to be stroked. When
+/// `closed` is `true`, the ends of the arc are connected to the center.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Arc {
+    pub radius: f32,
+    pub center: Vec2,
+    pub start_angle: f32,
+    pub sweep_angle: f32,
+    pub closed: bool,
+}
+
+impl Default for Arc {
+    fn default() -> Self {
+        Self {
+            radius: 1.0,
+            center: Vec2::ZERO,
+            start_angle: 0.0,
+            sweep_angle: std::f32::consts::PI,
+            closed: false,
+        }
+    }
+}
+
+impl Geometry for Arc {
+    fn add_geometry(&self, b: &mut Builder) {
+        let center = self.center.convert();
+        let start =
+            center + Vector::from_angle_and_length(Angle::radians(self.start_angle), self.radius);
+
+        b.begin(start);
+        arc_to(b, center, self.radius, self.start_angle, self.sweep_angle);
+        if self.closed {
+            b.line_to(center);
+        }
+        b.end(self.closed);
+    }
+}
+
+/// A circular sector (a pie slice). Angles are in radians, counterclockwise
+/// from the positive x-axis.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sector {
+    pub radius: f32,
+    pub center: Vec2,
+    pub start_angle: f32,
+    pub sweep_angle: f32,
+}
+
+impl Default for Sector {
+    fn default() -> Self {
+        Self {
+            radius: 1.0,
+            center: Vec2::ZERO,
+            start_angle: 0.0,
+            sweep_angle: std::f32::consts::FRAC_PI_2,
+        }
+    }
+}
+
+impl Geometry for Sector {
+    fn add_geometry(&self, b: &mut Builder) {
+        let center = self.center.convert();
+        let start =
+            center + Vector::from_angle_and_length(Angle::radians(self.start_angle), self.radius);
+
+        b.begin(center);
+        b.line_to(start);
+        arc_to(b, center, self.radius, self.start_angle, self.sweep_angle);
+        b.close();
+    }
+}
+
+/// The style of an [`Arrow`] head.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArrowHead {
+    /// The shaft ends with a flat cap.
+    None,
+    /// A filled triangular head.
+    Triangle,
+    /// An open chevron, with arms as thick as the shaft.
+    Chevron,
+}
+
+impl ArrowHead {
+    /// Returns the outline of the head, for an arrow pointing towards the
+    /// positive x-axis with the tip at the origin. The points are ordered from
+    /// the bottom side of the shaft to the top side.
+    fn outline(self, half_shaft: f32, head_length: f32, head_width: f32) -> Vec<Vec2> {
+        let half_head = head_width / 2.0;
+        match self {
+            Self::None => vec![Vec2::new(0.0, -half_shaft), Vec2::new(0.0, half_shaft)],
+            Self::Triangle => vec![
+                Vec2::new(-head_length, -half_shaft),
+                Vec2::new(-head_length, -half_head),
+                Vec2::ZERO,
+                Vec2::new(-head_length, half_head),
+                Vec2::new(-head_length, half_shaft),
+            ],
+            // Without width, the arms of the chevron have no direction.
+            Self::Chevron if half_head.abs() <= f32::EPSILON => {
+                Self::None.outline(half_shaft, head_length, head_width)
+            }
+            Self::Chevron => {
+                // `arm`: direction from the tip to the end of the lower arm.
+                // `inward`: normal of the arm, pointing inside the chevron.
+                let arm_end = Vec2::new(-head_length, -half_head);
+                let arm = arm_end.normalize();
+                let inward = Vec2::new(arm.y, -arm.x) * half_shaft * 2.0;
+                let k = (-half_shaft - inward.y) / arm.y;
+                let notch = inward + arm * k;
+                let mirror = |v: Vec2| Vec2::new(v.x, -v.y);
+
+                vec![
+                    notch,
+                    arm_end + inward,
+                    arm_end,
+                    Vec2::ZERO,
+                    mirror(arm_end),
+                    mirror(arm_end + inward),
+                    mirror(notch),
+                ]
+            }
+        }
+    }
+}
+
+impl Default for ArrowHead {
+    fn default() -> Self {
+        Self::Triangle
+    }
+}
+
+/// An arrow from `start` to `end`, built as a single closed outline. Each end
+/// of the arrow can have a different [`ArrowHead`].
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Arrow {
+    pub start: Vec2,
+    pub end: Vec2,
+    pub shaft_width: f32,
+    pub head_length: f32,
+    pub head_width: f32,
+    pub start_head: ArrowHead,
+    pub end_head: ArrowHead,
+}
+
+impl Default for Arrow {
+    fn default() -> Self {
+        Self {
+            start: Vec2::ZERO,
+            end: Vec2::new(1.0, 0.0),
+            shaft_width: 0.1,
+            head_length: 0.3,
+            head_width: 0.3,
+            start_head: ArrowHead::None,
+            end_head: ArrowHead::Triangle,
+        }
+    }
+}
+
+impl Geometry for Arrow {
+    fn add_geometry(&self, b: &mut Builder) {
+        let axis = self.end - self.start;
+        let length = axis.length();
+        if length <= f32::EPSILON {
+            return;
+        }
+
+        let dir = axis / length;
+        let normal = Vec2::new(-dir.y, dir.x);
+        let to_world = |t: f32, s: f32| (self.start + dir * t + normal * s).convert();
+        let half_shaft = self.shaft_width / 2.0;
+
+        let end_head = self
+            .end_head
+            .outline(half_shaft, self.head_length, self.head_width);
+        let start_head = self
+            .start_head
+            .outline(half_shaft, self.head_length, self.head_width);
+
+        // The start head is the end head rotated by 180 degrees, so its points
+        // go from the top side of the shaft to the bottom side.
+        let points = end_head
+            .iter()
+            .map(|p| to_world(length + p.x, p.y))
+            .chain(start_head.iter().map(|p| to_world(-p.x, -p.y)))
+            .collect::<Vec<Point>>();
+
+        b.add_polygon(LyonPolygon {
+            points: points.as_slice(),
+            closed: true,
+        });
+    }
+}
+
+/// A plus sign, built as a single closed outline. `width` is the length of
+/// each bar, and `thickness` is their width. When `diagonal` is `true`, the
+/// cross is rotated by 45 degrees, resulting in an X.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cross {
+    pub width: f32,
+    pub thickness: f32,
+    pub center: Vec2,
+    pub diagonal: bool,
+}
+
+impl Default for Cross {
+    fn default() -> Self {
+        Self {
+            width: 1.0,
+            thickness: 0.25,
+            center: Vec2::ZERO,
+            diagonal: false,
+        }
+    }
+}
+
+impl Geometry for Cross {
+    fn add_geometry(&self, b: &mut Builder) {
+        let hw = self.width / 2.0;
+        let ht = self.thickness / 2.0;
+        let rotation = if self.diagonal {
+            Vec2::new(
+                std::f32::consts::FRAC_PI_4.cos(),
+                std::f32::consts::FRAC_PI_4.sin(),
+            )
+        } else {
+            Vec2::new(1.0, 0.0)
+        };
+
+        let points = [
+            (ht, -hw),
+            (ht, -ht),
+            (hw, -ht),
+            (hw, ht),
+            (ht, ht),
+            (ht, hw),
+            (-ht, hw),
+            (-ht, ht),
+            (-hw, ht),
+            (-hw, -ht),
+            (-ht, -ht),
+            (-ht, -hw),
+        ]
+        .iter()
+        .map(|&(x, y)| {
+            let rotated = Vec2::new(
+                x.mul_add(rotation.x, -y * rotation.y),
+                x.mul_add(rotation.y, y * rotation.x),
+            );
+            (self.center + rotated).convert()
+        })
+        .collect::<Vec<Point>>();
+
+        b.add_polygon(LyonPolygon {
+            points: points.as_slice(),
+            closed: true,
+        });
+    }
+}
+
+/// An angle bracket with its apex at `apex`, pointing towards the positive
+/// x-axis when `rotation` is zero. `apex_angle` is the angle between the two
+/// arms, in radians.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Chevron {
+    pub arm_length: f32,
+    pub thickness: f32,
+    pub apex_angle: f32,
+    pub apex: Vec2,
+    pub rotation: f32,
+}
+
+impl Default for Chevron {
+    fn default() -> Self {
+        Self {
+            arm_length: 1.0,
+            thickness: 0.25,
+            apex_angle: std::f32::consts::FRAC_PI_2,
+            apex: Vec2::ZERO,
+            rotation: 0.0,
+        }
+    }
+}
+
+impl Geometry for Chevron {
+    fn add_geometry(&self, b: &mut Builder) {
+        // -- Implementation details **PLEASE KEEP UPDATED** --
+        // - `arm`: direction from the apex to the end of the lower arm.
+        // - `inward`: normal of the lower arm, pointing inside the chevron.
+        // - `inner_apex`: the point where the inner edges of the arms meet. The apex
+        //   angle is kept away from `0` and `2π`, where the arms are parallel and never
+        //   meet.
+        const MIN_APEX_ANGLE: f32 = 1.0e-3;
+        let apex_angle = self
+            .apex_angle
+            .max(MIN_APEX_ANGLE)
+            .min(std::f32::consts::TAU - MIN_APEX_ANGLE);
+        let half_angle = apex_angle / 2.0;
+        let arm = Vec2::new(-half_angle.cos(), -half_angle.sin());
+        let arm_end = arm * self.arm_length;
+        let inward = Vec2::new(arm.y, -arm.x) * self.thickness;
+        let inner_apex = inward - arm * (inward.y / arm.y);
+        let mirror = |v: Vec2| Vec2::new(v.x, -v.y);
+
+        let (sin, cos) = self.rotation.sin_cos();
+        let points = [
+            Vec2::ZERO,
+            arm_end,
+            arm_end + inward,
+            inner_apex,
+            mirror(arm_end + inward),
+            mirror(arm_end),
+        ]
+        .iter()
+        .map(|v| {
+            let rotated = Vec2::new(v.x.mul_add(cos, -v.y * sin), v.x.mul_add(sin, v.y * cos));
+            (self.apex + rotated).convert()
+        })
+        .collect::<Vec<Point>>();
+
+        b.add_polygon(LyonPolygon {
+            points: points.as_slice(),
+            closed: true,
+        });
+    }
+}
+
+/// A heart, built from two cubic bézier curves. With a `scale` of `1.0`, the
+/// heart is about two units wide and two units tall, with its bottom tip at
+/// `(0, -1)` relative to the center.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Heart {
+    pub scale: f32,
+    pub center: Vec2,
+}
+
+impl Default for Heart {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            center: Vec2::ZERO,
+        }
+    }
+}
+
+impl Geometry for Heart {
+    fn add_geometry(&self, b: &mut Builder) {
+        let p = |x: f32, y: f32| (self.center + Vec2::new(x, y) * self.scale).convert();
+
+        b.begin(p(0.0, -1.0));
+        b.cubic_bezier_to(p(-1.6, 0.1), p(-0.8, 1.3), p(0.0, 0.5));
+        b.cubic_bezier_to(p(0.8, 1.3), p(1.6, 0.1), p(0.0, -1.0));
+        b.close();
+    }
+}
+
+/// A gear outline, with `teeth` teeth going from `inner_radius` to
+/// `outer_radius`. `tooth_width` is the width of each tooth, measured along
+/// the circles. When `hole_radius` is greater than zero, the gear also has a
+/// central hole.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Gear {
+    pub teeth: usize,
+    pub outer_radius: f32,
+    pub inner_radius: f32,
+    pub tooth_width: f32,
+    pub hole_radius: f32,
+    pub center: Vec2,
+}
+
+impl Default for Gear {
+    fn default() -> Self {
+        Self {
+            teeth: 8,
+            outer_radius: 1.0,
+            inner_radius: 0.8,
+            tooth_width: 0.3,
+            hole_radius: 0.0,
+            center: Vec2::ZERO,
+        }
+    }
+}
+
+impl Geometry for Gear {
+    fn add_geometry(&self, b: &mut Builder) {
+        // -- Implementation details **PLEASE KEEP UPDATED** --
+        // - `step`: angle between the centers of two teeth.
+        // - `base`: half of the angle covered by a tooth on the inner circle.
+        // - `top`: half of the angle covered by a tooth on the outer circle.
+
+        use std::f32::consts::PI;
+        assert!(self.teeth > 2, "Gears must have at least 3 teeth");
+        let center = self.center.convert();
+        let polar = |radius: f32, angle: f32| {
+            center + Vector::from_angle_and_length(Angle::radians(angle), radius)
+        };
+        let step = 2.0 * PI / self.teeth as f32;
+        let base = (self.tooth_width / self.inner_radius / 2.0).min(step / 2.0);
+        let top = (self.tooth_width / self.outer_radius / 2.0).min(base);
+
+        b.begin(polar(self.inner_radius, -base));
+        for i in 0..self.teeth {
+            let angle = i as f32 * step;
+            b.line_to(polar(self.outer_radius, angle - top));
+            arc_to(b, center, self.outer_radius, angle - top, 2.0 * top);
+            b.line_to(polar(self.inner_radius, angle + base));
+            arc_to(
+                b,
+                center,
+                self.inner_radius,
+                angle + base,
+                2.0f32.mul_add(-base, step),
+            );
+        }
+        b.close();
+
+        // The hole is wound clockwise, opposite to the teeth.
+        if self.hole_radius > 0.0 {
+            b.begin(polar(self.hole_radius, 0.0));
+            arc_to(b, center, self.hole_radius, 0.0, -2.0 * PI);
+            b.close();
+        }
+    }
+}
+
+/// An Archimedean spiral, starting at `start_radius` from the center and
+/// moving away from it by `spacing` after every turn. It is an open path,
+/// best suited to be stroked.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Spiral {
+    pub turns: f32,
+    pub spacing: f32,
+    pub start_radius: f32,
+    pub center: Vec2,
+}
+
+impl Default for Spiral {
+    fn default() -> Self {
+        Self {
+            turns: 3.0,
+            spacing: 0.25,
+            start_radius: 0.0,
+            center: Vec2::ZERO,
+        }
+    }
+}
+
+impl Geometry for Spiral {
+    #[allow(clippy::cast_sign_loss)] // `segments` is always positive
+    fn add_geometry(&self, b: &mut Builder) {
+        // The spiral is approximated with a cubic bézier for every eighth of a
+        // turn, with the control points derived from the curve tangents.
+        use std::f32::consts::PI;
+        const SEGMENTS_PER_TURN: f32 = 8.0;
+
+        let growth = self.spacing / (2.0 * PI);
+        let position = |angle: f32| {
+            let radius = growth.mul_add(angle, self.start_radius);
+            self.center + Vec2::new(angle.cos(), angle.sin()) * radius
+        };
+        let derivative = |angle: f32| {
+            let radius = growth.mul_add(angle, self.start_radius);
+            let (sin, cos) = angle.sin_cos();
+            Vec2::new(growth * cos, growth * sin) + Vec2::new(-sin, cos) * radius
+        };
+
+        let segments = (self.turns * SEGMENTS_PER_TURN).ceil().max(1.0) as usize;
+        let step = self.turns * 2.0 * PI / segments as f32;
+
+        b.begin(position(0.0).convert());
+        for i in 0..segments {
+            let from = i as f32 * step;
+            let to = from + step;
+            b.cubic_bezier_to(
+                (position(from) + derivative(from) * step / 3.0).convert(),
+                (position(to) - derivative(to) * step / 3.0).convert(),
+                position(to).convert(),
+            );
+        }
+        b.end(false);
+    }
+}
+
+/// The side of a [`SpeechBubble`] where the tail is placed.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BubbleEdge {
+    Bottom,
+    Right,
+    Top,
+    Left,
+}
+
+/// The tail of a [`SpeechBubble`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpeechBubbleTail {
+    /// The side where the tail is placed.
+    pub edge: BubbleEdge,
+    /// The position of the tail along its edge, from `0.0` (left or bottom
+    /// end) to `1.0` (right or top end).
+    pub position: f32,
+    /// The width of the tail where it joins the bubble.
+    pub width: f32,
+    /// The distance between the tip of the tail and the bubble.
+    pub length: f32,
+}
+
+impl Default for SpeechBubbleTail {
+    fn default() -> Self {
+        Self {
+            edge: BubbleEdge::Bottom,
+            position: 0.25,
+            width: 0.2,
+            length: 0.3,
+        }
+    }
+}
+
+/// A rounded rectangle with a triangular tail, built as a single closed
+/// outline.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpeechBubble {
+    pub width: f32,
+    pub height: f32,
+    pub corner_radius: f32,
+    pub origin: RectangleOrigin,
+    pub tail: SpeechBubbleTail,
+}
+
+impl Default for SpeechBubble {
+    fn default() -> Self {
+        Self {
+            width: 2.0,
+            height: 1.0,
+            corner_radius: 0.2,
+            origin: RectangleOrigin::default(),
+            tail: SpeechBubbleTail::default(),
+        }
+    }
+}
+
+impl Geometry for SpeechBubble {
+    fn add_geometry(&self, b: &mut Builder) {
+        use std::f32::consts::{FRAC_PI_2, PI};
+
+        let origin = self.origin.bottom_left(self.width, self.height);
+        let (x, y) = (origin.x, origin.y);
+        let (width, height) = (self.width, self.height);
+        // Unlike `clamp`, this doesn't panic for a negative or NaN size.
+        let r = self
+            .corner_radius
+            .max(0.0)
+            .min((width.min(height) / 2.0).max(0.0));
+
+        // The outline is traversed counterclockwise: every edge is followed by
+        // the corner at its end.
+        let edges = [
+            (point(x + r, y), point(x + width - r, y), BubbleEdge::Bottom),
+            (
+                point(x + width, y + r),
+                point(x + width, y + height - r),
+                BubbleEdge::Right,
+            ),
+            (
+                point(x + width - r, y + height),
+                point(x + r, y + height),
+                BubbleEdge::Top,
+            ),
+            (point(x, y + height - r), point(x, y + r), BubbleEdge::Left),
+        ];
+        let corners = [
+            (point(x + width - r, y + r), -FRAC_PI_2),
+            (point(x + width - r, y + height - r), 0.0),
+            (point(x + r, y + height - r), FRAC_PI_2),
+            (point(x + r, y + r), PI),
+        ];
+
+        b.begin(edges[0].0);
+        for (&(from, to, edge), &(corner, angle)) in edges.iter().zip(corners.iter()) {
+            let length = (to - from).length();
+            if edge == self.tail.edge && length > 0.0 {
+                let dir = (to - from) / length;
+                let outward = Vector::new(dir.y, -dir.x);
+                let position = match edge {
+                    BubbleEdge::Bottom | BubbleEdge::Right => self.tail.position,
+                    BubbleEdge::Top | BubbleEdge::Left => 1.0 - self.tail.position,
+                };
+                let half_width = (self.tail.width / 2.0).min(length / 2.0);
+                let distance = (position * length).clamp(half_width, length - half_width);
+                let base = from + dir * distance;
+
+                b.line_to(base - dir * half_width);
+                b.line_to(base + outward * self.tail.length);
+                b.line_to(base + dir * half_width);
+            }
+            b.line_to(to);
+            arc_to(b, corner, r, angle, FRAC_PI_2);
+        }
+        b.close();
+    }
+}
+
+/// The lines of a grid with `columns` by `rows` cells, each one of size
+/// `cell_size`. Every line is a separate open sub-path, so the whole grid can
+/// be drawn as a single stroked shape.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Grid {
+    pub columns: usize,
+    pub rows: usize,
+    pub cell_size: Vec2,
+    pub origin: RectangleOrigin,
+}
+
+impl Default for Grid {
+    fn default() -> Self {
+        Self {
+            columns: 8,
+            rows: 8,
+            cell_size: Vec2::ONE,
+            origin: RectangleOrigin::default(),
+        }
+    }
+}
+
+impl Geometry for Grid {
+    fn add_geometry(&self, b: &mut Builder) {
+        let width = self.columns as f32 * self.cell_size.x;
+        let height = self.rows as f32 * self.cell_size.y;
+        let origin = self.origin.bottom_left(width, height);
+
+        for column in 0..=self.columns {
+            let x = (column as f32).mul_add(self.cell_size.x, origin.x);
+            b.begin(point(x, origin.y));
+            b.line_to(point(x, origin.y + height));
+            b.end(false);
+        }
+        for row in 0..=self.rows {
+            let y = (row as f32).mul_add(self.cell_size.y, origin.y);
+            b.begin(point(origin.x, y));
+            b.line_to(point(origin.x + width, y));
+            b.end(false);
+        }
+    }
+}
+
+/// A quadratic bézier curve. It is an open path, best suited to be stroked.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuadraticBezier {
+    pub from: Vec2,
+    pub ctrl: Vec2,
+    pub to: Vec2,
+}
+
+impl Default for QuadraticBezier {
+    fn default() -> Self {
+        Self {
+            from: Vec2::ZERO,
+            ctrl: Vec2::new(0.5, 1.0),
+            to: Vec2::new(1.0, 0.0),
+        }
+    }
+}
+
+impl Geometry for QuadraticBezier {
+    fn add_geometry(&self, b: &mut Builder) {
+        b.begin(self.from.convert());
+        b.quadratic_bezier_to(self.ctrl.convert(), self.to.convert());
+        b.end(false);
+    }
+}
+
+/// A cubic bézier curve. It is an open path, best suited to be stroked.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CubicBezier {
+    pub from: Vec2,
+    pub ctrl1: Vec2,
+    pub ctrl2: Vec2,
+    pub to: Vec2,
+}
+
+impl Default for CubicBezier {
+    fn default() -> Self {
+        Self {
+            from: Vec2::ZERO,
+            ctrl1: Vec2::new(0.0, 1.0),
+            ctrl2: Vec2::new(1.0, 1.0),
+            to: Vec2::new(1.0, 0.0),
+        }
+    }
+}
+
+impl Geometry for CubicBezier {
+    fn add_geometry(&self, b: &mut Builder) {
+        b.begin(self.from.convert());
+        b.cubic_bezier_to(
+            self.ctrl1.convert(),
+            self.ctrl2.convert(),
+            self.to.convert(),
+        );
+        b.end(false);
+    }
+}
+
+/// A smooth curve passing through all the given points, built as a cardinal
+/// spline. A `tension` of `0.0` gives a Catmull-Rom spline, while higher
+/// values tighten the curve, up to straight lines at `1.0`.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SmoothPolyline {
+    pub points: Vec<Vec2>,
+    pub tension: f32,
+    pub closed: bool,
+}
+
+impl Default for SmoothPolyline {
+    fn default() -> Self {
+        Self {
+            points: Vec::new(),
+            tension: 0.0,
+            closed: false,
+        }
+    }
+}
+
+/// Creates an open curve with the default tension.
+impl From<&[Vec2]> for SmoothPolyline {
+    fn from(points: &[Vec2]) -> Self {
+        Self {
+            points: points.to_vec(),
+            ..Self::default()
+        }
+    }
+}
+
+impl Geometry for SmoothPolyline {
+    fn add_geometry(&self, b: &mut Builder) {
+        add_cardinal_spline(b, &self.points, self.tension, self.closed);
+    }
+}
+
+/// A sine wave along the x-axis, starting at `start`. It is an open path,
+/// best suited to be stroked.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Wave {
+    pub amplitude: f32,
+    pub wavelength: f32,
+    pub cycles: f32,
+    pub start: Vec2,
+}
+
+impl Default for Wave {
+    fn default() -> Self {
+        Self {
+            amplitude: 0.5,
+            wavelength: 1.0,
+            cycles: 3.0,
+            start: Vec2::ZERO,
+        }
+    }
+}
+
+impl Geometry for Wave {
+    #[allow(clippy::cast_sign_loss)] // `segments` is always positive
+    fn add_geometry(&self, b: &mut Builder) {
+        // The wave is approximated with a cubic bézier for every quarter of a
+        // cycle, with the control points derived from the curve tangents.
+        use std::f32::consts::PI;
+        const SEGMENTS_PER_CYCLE: f32 = 4.0;
+
+        let k = 2.0 * PI / self.wavelength;
+        let position = |x: f32| self.start + Vec2::new(x, self.amplitude * (k * x).sin());
+        let tangent = |x: f32| Vec2::new(1.0, self.amplitude * k * (k * x).cos());
+
+        let segments = (self.cycles * SEGMENTS_PER_CYCLE).ceil().max(1.0) as usize;
+        let step = self.cycles * self.wavelength / segments as f32;
+
+        b.begin(position(0.0).convert());
+        for i in 0..segments {
+            let from = i as f32 * step;
+            let to = from + step;
+            b.cubic_bezier_to(
+                (position(from) + tangent(from) * step / 3.0).convert(),
+                (position(to) - tangent(to) * step / 3.0).convert(),
+                position(to).convert(),
+            );
+        }
+        b.end(false);
+    }
+}
+
+/// A jagged line between two points, like a lightning bolt or a crack.
+///
+/// The line is split into `segments` parts, and every inner vertex is moved
+/// perpendicularly to the line by a random amount up to `displacement`. The
+/// randomness only depends on `seed`, so the same shape is always reproduced.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JaggedLine {
+    pub start: Vec2,
+    pub end: Vec2,
+    pub segments: usize,
+    pub displacement: f32,
+    pub seed: u64,
+}
+
+impl Default for JaggedLine {
+    fn default() -> Self {
+        Self {
+            start: Vec2::ZERO,
+            end: Vec2::new(1.0, 0.0),
+            segments: 8,
+            displacement: 0.1,
+            seed: 0,
+        }
+    }
+}
+
+impl Geometry for JaggedLine {
+    fn add_geometry(&self, b: &mut Builder) {
+        let segments = self.segments.max(1);
+        let axis = self.end - self.start;
+        let normal = if axis.length_squared() > 0.0 {
+            Vec2::new(-axis.y, axis.x).normalize()
+        } else {
+            Vec2::ZERO
+        };
+        let mut rng = SplitMix64::new(self.seed);
+
+        b.begin(self.start.convert());
+        for i in 1..segments {
+            let along = self.start + axis * (i as f32 / segments as f32);
+            let offset = normal * rng.next_signed() * self.displacement;
+            b.line_to((along + offset).convert());
+        }
+        b.line_to(self.end.convert());
+        b.end(false);
+    }
+}
+
+/// A smooth, organic closed shape.
+///
+/// `points` vertices are placed on a circle, and their distance from the
+/// center is randomly changed by up to `irregularity` times the radius. A
+/// smooth curve is then fitted through them. The randomness only depends on
+/// `seed`, so the same shape is always reproduced.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Blob {
+    pub radius: f32,
+    pub points: usize,
+    pub irregularity: f32,
+    pub seed: u64,
+    pub center: Vec2,
+}
+
+impl Default for Blob {
+    fn default() -> Self {
+        Self {
+            radius: 1.0,
+            points: 8,
+            irregularity: 0.2,
+            seed: 0,
+            center: Vec2::ZERO,
+        }
+    }
+}
+
+impl Geometry for Blob {
+    fn add_geometry(&self, b: &mut Builder) {
+        use std::f32::consts::PI;
+        assert!(self.points > 2, "Blobs must have at least 3 points");
+        let mut rng = SplitMix64::new(self.seed);
+        let step = 2.0 * PI / self.points as f32;
+
+        let points = (0..self.points)
+            .map(|i| {
+                let angle = i as f32 * step;
+                let radius = self.radius * self.irregularity.mul_add(rng.next_signed(), 1.0);
+                self.center + Vec2::new(angle.cos(), angle.sin()) * radius
+            })
+            .collect::<Vec<Vec2>>();
+
+        add_cardinal_spline(b, &points, 0.0, true);
+    }
+}
+
+/// A triangle, specified by its three vertices.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Triangle(pub Vec2, pub Vec2, pub Vec2);
+
+impl Triangle {
+    /// Creates an equilateral triangle with the given side length, pointing
+    /// upwards, with its centroid at the origin.
+    #[must_use]
+    pub fn equilateral(side: f32) -> Self {
+        Self::isosceles(side, side * 3.0_f32.sqrt() / 2.0)
+    }
+
+    /// Creates an isosceles triangle with the given base and height, pointing
+    /// upwards, with its centroid at the origin.
+    #[must_use]
+    pub fn isosceles(base: f32, height: f32) -> Self {
+        Self(
+            Vec2::new(-base / 2.0, -height / 3.0),
+            Vec2::new(base / 2.0, -height / 3.0),
+            Vec2::new(0.0, height * 2.0 / 3.0),
+        )
+    }
+}
+
+impl Geometry for Triangle {
+    fn add_geometry(&self, b: &mut Builder) {
+        b.add_polygon(LyonPolygon {
+            points: &[self.0.convert(), self.1.convert(), self.2.convert()],
+            closed: true,
+        });
+    }
+}
+
+/// A parallelogram with horizontal bases. `skew` is the horizontal offset of
+/// the top side relative to the bottom side.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Parallelogram {
+    pub base: f32,
+    pub height: f32,
+    pub skew: f32,
+    pub center: Vec2,
+}
+
+impl Default for Parallelogram {
+    fn default() -> Self {
+        Self {
+            base: 1.0,
+            height: 1.0,
+            skew: 0.5,
+            center: Vec2::ZERO,
+        }
+    }
+}
+
+impl Geometry for Parallelogram {
+    fn add_geometry(&self, b: &mut Builder) {
+        let (hb, hh, hs) = (self.base / 2.0, self.height / 2.0, self.skew / 2.0);
+        let p = |x: f32, y: f32| (self.center + Vec2::new(x, y)).convert();
+
+        b.add_polygon(LyonPolygon {
+            points: &[
+                p(-hb - hs, -hh),
+                p(hb - hs, -hh),
+                p(hb + hs, hh),
+                p(-hb + hs, hh),
+            ],
+            closed: true,
+        });
+    }
+}
+
+/// A trapezoid with horizontal, vertically centered bases.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Trapezoid {
+    pub top_width: f32,
+    pub bottom_width: f32,
+    pub height: f32,
+    pub center: Vec2,
+}
+
+impl Default for Trapezoid {
+    fn default() -> Self {
+        Self {
+            top_width: 0.5,
+            bottom_width: 1.0,
+            height: 1.0,
+            center: Vec2::ZERO,
+        }
+    }
+}
+
+impl Geometry for Trapezoid {
+    fn add_geometry(&self, b: &mut Builder) {
+        let (ht, hb, hh) = (
+            self.top_width / 2.0,
+            self.bottom_width / 2.0,
+            self.height / 2.0,
+        );
+        let p = |x: f32, y: f32| (self.center + Vec2::new(x, y)).convert();
+
+        b.add_polygon(LyonPolygon {
+            points: &[p(-hb, -hh), p(hb, -hh), p(ht, hh), p(-ht, hh)],
+            closed: true,
+        });
+    }
+}
+
+/// A rhombus (or diamond), with its vertices lying on the axes passing
+/// through the center.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rhombus {
+    pub width: f32,
+    pub height: f32,
+    pub center: Vec2,
+}
+
+impl Default for Rhombus {
+    fn default() -> Self {
+        Self {
+            width: 1.0,
+            height: 1.0,
+            center: Vec2::ZERO,
+        }
+    }
+}
+
+impl Geometry for Rhombus {
+    fn add_geometry(&self, b: &mut Builder) {
+        let (hw, hh) = (self.width / 2.0, self.height / 2.0);
+        let p = |x: f32, y: f32| (self.center + Vec2::new(x, y)).convert();
+
+        b.add_polygon(LyonPolygon {
+            points: &[p(0.0, -hh), p(hw, 0.0), p(0.0, hh), p(-hw, 0.0)],
+            closed: true,
+        });
+    }
+}
+
+/// The part of a circle above a horizontal chord. `chord_offset` is the
+/// vertical position of the chord relative to the center, and must be between
+/// `-radius` and `radius`.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CircularSegment {
+    pub radius: f32,
+    pub chord_offset: f32,
+    pub center: Vec2,
+}
+
+impl Default for CircularSegment {
+    fn default() -> Self {
+        Self {
+            radius: 1.0,
+            chord_offset: 0.5,
+            center: Vec2::ZERO,
+        }
+    }
+}
+
+impl Geometry for CircularSegment {
+    fn add_geometry(&self, b: &mut Builder) {
+        use std::f32::consts::PI;
+
+        // Unlike `clamp`, this doesn't panic for a negative or NaN radius.
+        let max_offset = self.radius.abs();
+        let offset = self.chord_offset.max(-max_offset).min(max_offset);
+        let start_angle = (offset / self.radius).asin();
+        let center = self.center.convert();
+        let start =
+            center + Vector::from_angle_and_length(Angle::radians(start_angle), self.radius);
+
+        b.begin(start);
+        arc_to(
+            b,
+            center,
+            self.radius,
+            start_angle,
+            2.0f32.mul_add(-start_angle, PI),
+        );
+        b.close();
+    }
+}
+
+/// The upper half of a circle.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Semicircle {
+    pub radius: f32,
+    pub center: Vec2,
+}
+
+impl Default for Semicircle {
+    fn default() -> Self {
+        Self {
+            radius: 1.0,
+            center: Vec2::ZERO,
+        }
+    }
+}
+
+impl Geometry for Semicircle {
+    fn add_geometry(&self, b: &mut Builder) {
+        CircularSegment {
+            radius: self.radius,
+            chord_offset: 0.0,
+            center: self.center,
+        }
+        .add_geometry(b);
+    }
+}
+
+/// A crescent, obtained by cutting a circle with another circle of the same
+/// radius, shifted by `offset` towards the positive x-axis. Larger offsets give
+/// thicker crescents.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Crescent {
+    pub radius: f32,
+    pub offset: f32,
+    pub center: Vec2,
+}
+
+impl Default for Crescent {
+    fn default() -> Self {
+        Self {
+            radius: 1.0,
+            offset: 0.5,
+            center: Vec2::ZERO,
+        }
+    }
+}
+
+impl Geometry for Crescent {
+    fn add_geometry(&self, b: &mut Builder) {
+        // -- Implementation details **PLEASE KEEP UPDATED** --
+        // - `angle`: angle of the upper intersection point of the two circles, measured
+        //   from the center of the outer circle. Seen from the center of the cutting
+        //   circle, the same point has an angle of `PI - angle`.
+
+        use std::f32::consts::PI;
+        let center = self.center.convert();
+        let offset = self.offset.abs();
+        if offset <= 0.0 {
+            return;
+        }
+        if offset >= 2.0 * self.radius {
+            b.add_circle(center, self.radius, Winding::Positive);
+            return;
+        }
+
+        let half = offset / 2.0;
+        let angle = (self.radius.mul_add(self.radius, -half * half))
+            .sqrt()
+            .atan2(half);
+        let cut_center = center + Vector::new(offset, 0.0);
+
+        b.begin(center + Vector::from_angle_and_length(Angle::radians(angle), self.radius));
+        arc_to(
+            b,
+            center,
+            self.radius,
+            angle,
+            2.0f32.mul_add(-angle, 2.0 * PI),
+        );
+        arc_to(b, cut_center, self.radius, angle - PI, -2.0 * angle);
+        b.close();
+    }
+}
+
+/// A sector of a [`Ring`], delimited by two arcs and two radial edges. Angles
+/// are in radians, counterclockwise from the positive x-axis.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnnularSector {
+    pub inner_radius: f32,
+    pub outer_radius: f32,
+    pub start_angle: f32,
+    pub sweep_angle: f32,
+    pub center: Vec2,
+}
+
+impl Default for AnnularSector {
+    fn default() -> Self {
+        Self {
+            inner_radius: 0.5,
+            outer_radius: 1.0,
+            start_angle: 0.0,
+            sweep_angle: std::f32::consts::FRAC_PI_2,
+            center: Vec2::ZERO,
+        }
+    }
+}
+
+impl Geometry for AnnularSector {
+    fn add_geometry(&self, b: &mut Builder) {
+        let center = self.center.convert();
+        let end_angle = self.start_angle + self.sweep_angle;
+        let polar = |radius: f32, angle: f32| {
+            center + Vector::from_angle_and_length(Angle::radians(angle), radius)
+        };
+
+        b.begin(polar(self.outer_radius, self.start_angle));
+        arc_to(
+            b,
+            center,
+            self.outer_radius,
+            self.start_angle,
+            self.sweep_angle,
+        );
+        b.line_to(polar(self.inner_radius, end_angle));
+        arc_to(b, center, self.inner_radius, end_angle, -self.sweep_angle);
+        b.close();
+    }
+}
+
+/// A map pin (or teardrop): a circular head tapering to a point at `tip`.
+/// `height` is the distance from the tip to the top of the head. When
+/// `hole_radius` is greater than zero, the head also has a concentric hole.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pin {
+    pub head_radius: f32,
+    pub height: f32,
+    pub hole_radius: f32,
+    pub tip: Vec2,
+}
+
+impl Default for Pin {
+    fn default() -> Self {
+        Self {
+            head_radius: 0.5,
+            height: 1.5,
+            hole_radius: 0.0,
+            tip: Vec2::ZERO,
+        }
+    }
+}
+
+impl Geometry for Pin {
+    fn add_geometry(&self, b: &mut Builder) {
+        // -- Implementation details **PLEASE KEEP UPDATED** --
+        // - `distance`: distance between the tip and the center of the head.
+        // - `spread`: angle between the downward direction and the tangent points of
+        //   the lines from the tip to the head, seen from the center.
+
+        use std::f32::consts::{FRAC_PI_2, PI};
+        let radius = self.head_radius;
+        let distance = self.height - radius;
+        let head = self.tip.convert() + Vector::new(0.0, distance);
+        let polar = |length: f32, angle: f32| {
+            head + Vector::from_angle_and_length(Angle::radians(angle), length)
+        };
+
+        if distance > radius {
+            let spread = (radius / distance).acos();
+            b.begin(self.tip.convert());
+            b.line_to(polar(radius, spread - FRAC_PI_2));
+            arc_to(
+                b,
+                head,
+                radius,
+                spread - FRAC_PI_2,
+                2.0f32.mul_add(-spread, 2.0 * PI),
+            );
+            b.close();
+        } else {
+            b.begin(polar(radius, 0.0));
+            arc_to(b, head, radius, 0.0, 2.0 * PI);
+            b.close();
+        }
+
+        // The hole is wound clockwise, opposite to the outline.
+        if self.hole_radius > 0.0 {
+            b.begin(polar(self.hole_radius, 0.0));
+            arc_to(b, head, self.hole_radius, 0.0, -2.0 * PI);
+            b.close();
+        }
+    }
+}
+
+/// A polygon with rounded corners. Every corner is replaced by a circular arc
+/// of the given radius, which is reduced where the edges are too short to fit
+/// it.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoundedPolygon {
+    pub points: Vec<Vec2>,
+    pub radius: f32,
+    pub closed: bool,
+}
+
+impl Default for RoundedPolygon {
+    fn default() -> Self {
+        Self {
+            points: Vec::new(),
+            radius: 0.0,
+            closed: true,
+        }
+    }
+}
+
+impl Geometry for RoundedPolygon {
+    fn add_geometry(&self, b: &mut Builder) {
+        add_rounded_polyline(b, &self.points, self.radius, self.closed);
+    }
+}
+
+/// An egg shape, built from four quarter-ellipse cubic bézier curves.
+/// `asymmetry` ranges from `0.0` (a regular ellipse) to `1.0`, and moves the
+/// widest point of the egg towards the bottom.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Egg {
+    pub width: f32,
+    pub height: f32,
+    pub asymmetry: f32,
+    pub center: Vec2,
+}
+
+impl Default for Egg {
+    fn default() -> Self {
+        Self {
+            width: 0.8,
+            height: 1.0,
+            asymmetry: 0.2,
+            center: Vec2::ZERO,
+        }
+    }
+}
+
+impl Geometry for Egg {
+    fn add_geometry(&self, b: &mut Builder) {
+        // -- Implementation details **PLEASE KEEP UPDATED** --
+        // - `KAPPA`: distance of the control points of a cubic bézier approximating a
+        //   quarter of a unit circle.
+        // - `top`, `bottom`: vertical radii of the upper and lower half.
+        // - `widest`: height of the widest point of the egg.
+        const KAPPA: f32 = 0.552_284_8;
+
+        let asymmetry = self.asymmetry.clamp(0.0, 1.0);
+        let radius = self.width / 2.0;
+        let top = self.height / 2.0 * (1.0 + asymmetry);
+        let bottom = self.height - top;
+        let widest = bottom - self.height / 2.0;
+        let p = |x: f32, y: f32| (self.center + Vec2::new(x, y)).convert();
+        let (kx, bottom_ky, top_ky) = (KAPPA * radius, KAPPA * bottom, KAPPA * top);
+        let (low, high) = (-self.height / 2.0, self.height / 2.0);
+
+        b.begin(p(0.0, low));
+        b.cubic_bezier_to(p(kx, low), p(radius, widest - bottom_ky), p(radius, widest));
+        b.cubic_bezier_to(p(radius, widest + top_ky), p(kx, high), p(0.0, high));
+        b.cubic_bezier_to(
+            p(-kx, high),
+            p(-radius, widest + top_ky),
+            p(-radius, widest),
+        );
+        b.cubic_bezier_to(p(-radius, widest - bottom_ky), p(-kx, low), p(0.0, low));
+        b.close();
+    }
+}
+
+/// The convex hull of a set of points, that is the smallest convex polygon
+/// containing all of them. The hull is wound counter-clockwise, and collinear
+/// points on its edges are left out.
+///
+/// Nothing is drawn if there are less than two distinct points.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ConvexHull(pub Vec<Vec2>);
+
+// -- Implementation details **PLEASE KEEP UPDATED** --
+// The hull is computed with Andrew's monotone chain algorithm. The points are
+// sorted by x, then by y. The lower half of the hull is built by walking the
+// sorted points forwards, and the upper half by walking them backwards. Before
+// adding a point, the last point of the current half is discarded while it
+// doesn't make a counter-clockwise turn.
+impl Geometry for ConvexHull {
+    fn add_geometry(&self, b: &mut Builder) {
+        let mut points = self.0.clone();
+        points.sort_by(|p, q| {
+            (p.x, p.y)
+                .partial_cmp(&(q.x, q.y))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        points.dedup();
+        if points.len() < 2 {
+            return;
+        }
+
+        let mut hull = Vec::with_capacity(points.len() + 1);
+        for &point in &points {
+            push_hull_point(&mut hull, 2, point);
+        }
+        let lower_len = hull.len();
+        for &point in points.iter().rev().skip(1) {
+            push_hull_point(&mut hull, lower_len + 1, point);
+        }
+        // The upper half ends on the first point of the lower half.
+        hull.pop();
+
+        let hull = hull.iter().map(|p| p.convert()).collect::<Vec<Point>>();
+        b.add_polygon(LyonPolygon {
+            points: &hull,
+            closed: true,
+        });
+    }
+}
+
+/// The graph of a function `y = f(x)`, sampled at `samples` evenly spaced
+/// values of `x` between `start` and `end`, and joined by straight lines.
+///
+/// The curve is broken where the function returns `NaN` or an infinite value,
+/// so functions with poles can be plotted as well. The shape is made of open
+/// sub-paths, so it is best suited to be stroked.
+///
+/// # Example
+///
+/// ```
+/// use bevy_prototype_lyon::shapes::FunctionPlot;
+///
+/// let plot = FunctionPlot {
+///     function: |x: f32| x.sin() * 50.0,
+///     start: -300.0,
+///     end: 300.0,
+///     samples: 200,
+/// };
+/// ```
+#[allow(missing_docs)]
+#[derive(Clone, Copy)]
+pub struct FunctionPlot<F: Fn(f32) -> f32> {
+    pub function: F,
+    pub start: f32,
+    pub end: f32,
+    pub samples: usize,
+}
+
+impl<F: Fn(f32) -> f32> Geometry for FunctionPlot<F> {
+    fn add_geometry(&self, b: &mut Builder) {
+        let steps = self.samples.max(2) - 1;
+        let mut drawing = false;
+        for i in 0..=steps {
+            let x = (self.end - self.start).mul_add(i as f32 / steps as f32, self.start);
+            let y = (self.function)(x);
+            if !y.is_finite() {
+                if drawing {
+                    b.end(false);
+                    drawing = false;
+                }
+                continue;
+            }
+
+            if drawing {
+                b.line_to(point(x, y));
+            } else {
+                b.begin(point(x, y));
+                drawing = true;
+            }
+        }
+        if drawing {
+            b.end(false);
+        }
+    }
+}
+
+/// A line of text, drawn using the glyph outlines of a TrueType or OpenType
+/// font. Requires the `text` feature.
+///
+/// `font` is the content of the font file, and `size` is the height of the
+/// em square. `origin` is the starting point of the baseline of the first
+/// line, and every newline character in `text` starts a new line below.
+///
+/// Nothing is drawn if the font cannot be parsed. Characters missing from the
+/// font are drawn with its replacement glyph.
+///
+/// # Example
+///
+/// ```no_run
+/// use bevy::math::Vec2;
+/// use bevy_prototype_lyon::shapes;
+///
+/// let font = std::fs::read("assets/fonts/FiraSans-Bold.ttf").unwrap();
+/// let text = shapes::Text {
+///     font: &font,
+///     text: "Hello, world!",
+///     size: 48.0,
+///     origin: Vec2::ZERO,
+/// };
+/// ```
+#[cfg(feature = "text")]
+#[allow(missing_docs)]
+#[derive(Clone, Copy, PartialEq)]
+pub struct Text<'a> {
+    pub font: &'a [u8],
+    pub text: &'a str,
+    pub size: f32,
+    pub origin: Vec2,
+}
+
+#[cfg(feature = "text")]
+impl std::fmt::Debug for Text<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Text")
+            .field("font", &format_args!("[{} bytes]", self.font.len()))
+            .field("text", &self.text)
+            .field("size", &self.size)
+            .field("origin", &self.origin)
+            .finish()
+    }
+}
+
+#[cfg(feature = "text")]
+impl Text<'_> {
+    /// Adds the outlines of the glyphs to a path builder. `place` receives the
+    /// position of every glyph as if the text was laid out normally, and its
+    /// advance width. It returns where the origin of the glyph must be placed
+    /// and the direction of its baseline as a unit vector, or `None` to skip
+    /// the glyph.
+    pub(crate) fn add_glyphs(
+        &self,
+        b: &mut Builder,
+        mut place: impl FnMut(Vec2, f32) -> Option<(Vec2, Vec2)>,
+    ) {
+        let face = match ttf_parser::Face::from_slice(self.font, 0) {
+            Ok(face) => face,
+            Err(_) => return,
+        };
+        let scale = self.size / f32::from(face.units_per_em().unwrap_or(1000));
+        let line_height = (f32::from(face.ascender()) - f32::from(face.descender())
+            + f32::from(face.line_gap()))
+            * scale;
+
+        let mut pen = self.origin;
+        for c in self.text.chars() {
+            if c == '\n' {
+                pen = Vec2::new(self.origin.x, pen.y - line_height);
+                continue;
+            }
+
+            let glyph = face.glyph_index(c).unwrap_or(ttf_parser::GlyphId(0));
+            let advance = f32::from(face.glyph_hor_advance(glyph).unwrap_or(0)) * scale;
+            if let Some((offset, direction)) = place(pen, advance) {
+                face.outline_glyph(
+                    glyph,
+                    &mut GlyphOutline {
+                        builder: b,
+                        offset,
+                        direction,
+                        scale,
+                    },
+                );
+            }
+            pen.x += advance;
+        }
+    }
+}
+
+#[cfg(feature = "text")]
+impl Geometry for Text<'_> {
+    fn add_geometry(&self, b: &mut Builder) {
+        self.add_glyphs(b, |pen, _| Some((pen, Vec2::new(1.0, 0.0))));
+    }
+}
+
+/// Adds the outline of a glyph to a path builder, scaling it from font units
+/// and moving it to the position of the glyph, with its baseline along
+/// `direction`.
+#[cfg(feature = "text")]
+struct GlyphOutline<'b> {
+    builder: &'b mut Builder,
+    offset: Vec2,
+    direction: Vec2,
+    scale: f32,
+}
+
+#[cfg(feature = "text")]
+impl GlyphOutline<'_> {
+    fn point(&self, x: f32, y: f32) -> Point {
+        let (x, y) = (x * self.scale, y * self.scale);
+        let (cos, sin) = (self.direction.x, self.direction.y);
+        point(
+            x.mul_add(cos, -y * sin) + self.offset.x,
+            x.mul_add(sin, y * cos) + self.offset.y,
+        )
+    }
+}
+
+#[cfg(feature = "text")]
+impl ttf_parser::OutlineBuilder for GlyphOutline<'_> {
+    fn move_to(&mut self, x: f32, y: f32) {
+        let at = self.point(x, y);
+        self.builder.begin(at);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let to = self.point(x, y);
+        self.builder.line_to(to);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let (ctrl, to) = (self.point(x1, y1), self.point(x, y));
+        self.builder.quadratic_bezier_to(ctrl, to);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let (ctrl1, ctrl2, to) = (self.point(x1, y1), self.point(x2, y2), self.point(x, y));
+        self.builder.cubic_bezier_to(ctrl1, ctrl2, to);
+    }
+
+    fn close(&mut self) {
+        self.builder.end(true);
+    }
+}
+
+///An easy way to display svg paths as a shape, takes an svg path string and a
+///document size(Vec2).
+///
+///For documentation on svg paths: <https://developer.mozilla.org/en-US/docs/Web/SVG/Tutorial/Paths>
+///
+///Make sure that your units are pixels(px) and that the transform of the \<g\>
+///in your svg document is set to transform="translate(0,0)" so as to not
+///offset the coordinates of the paths
+///
+///In inkscape for example, to turn your units into pixels, you:
+///1) Go to File>Document Properties>General>Display Units and set it to px
+///
+///2) In File>Document Properties>Custom Size>Units set it to px, also, this
+/// size would be used for `svg_doc_size_in_px`
+///
+///3) In File>Document Properties>Scale>Scale x make sure it is set to 1 User
+/// unit per px
+///
+///Example exists in the examples folder
+#[derive(Debug, Clone, PartialEq)]
 pub struct SvgPathShape {
     ///The document size of the svg art, make sure the units are in pixels
     pub svg_doc_size_in_px: Vec2,
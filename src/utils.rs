@@ -3,10 +3,21 @@
 use bevy::math::Vec2;
 use lyon_tessellation::{
     math::{Point, Vector},
-    FillOptions, StrokeOptions,
+    FillOptions, FillRule, LineCap, LineJoin, StrokeOptions,
 };
 
 /// Determines how a shape will be drawn.
+///
+/// A shape can be made of many sub-paths. When filled, the sub-paths are
+/// combined according to the [`FillRule`] of the [`FillOptions`], which can be
+/// set with [`with_fill_rule`](Self::with_fill_rule):
+/// - with the default [`FillRule::NonZero`], a sub-path wound in the opposite
+///   direction of the one containing it becomes a hole, while a sub-path wound
+///   in the same direction is filled;
+/// - with [`FillRule::EvenOdd`], every sub-path inside another one becomes a
+///   hole, regardless of its direction. This is what most SVG art expects.
+///
+/// When stroked, every sub-path gets its own outline.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum DrawMode {
     /// The shape will be filled with the provided [`FillOptions`].
@@ -15,6 +26,10 @@ pub enum DrawMode {
     Stroke(StrokeOptions),
     /// The shape will be filled with the provided [`FillOptions`], then stroked
     /// with the [`StrokeOptions`], creating a shape with an outline.
+    ///
+    /// Both the fill and the outline are tessellated into the same mesh, with
+    /// the outline after the fill, so a single entity is enough and the
+    /// outline is always drawn on top of the fill.
     #[allow(missing_docs)]
     Outlined {
         fill_options: FillOptions,
@@ -22,7 +37,363 @@ pub enum DrawMode {
     },
 }
 
+impl DrawMode {
+    /// Returns the draw mode with the given fill rule. Has no effect on
+    /// [`DrawMode::Stroke`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bevy_prototype_lyon::prelude::*;
+    ///
+    /// let mode = DrawMode::Fill(FillOptions::default()).with_fill_rule(FillRule::EvenOdd);
+    /// assert_eq!(mode.fill_rule(), Some(FillRule::EvenOdd));
+    /// ```
+    #[must_use]
+    pub fn with_fill_rule(self, rule: FillRule) -> Self {
+        match self {
+            Self::Fill(options) => Self::Fill(options.with_fill_rule(rule)),
+            Self::Stroke(_) => self,
+            Self::Outlined {
+                fill_options,
+                outline_options,
+            } => Self::Outlined {
+                fill_options: fill_options.with_fill_rule(rule),
+                outline_options,
+            },
+        }
+    }
+
+    /// Returns the draw mode with the given tessellation tolerance, for both
+    /// the fill and the outline.
+    ///
+    /// The tolerance is the maximum distance between the curves of the shape
+    /// and the segments approximating them. Large shapes, like backgrounds, can
+    /// use a higher tolerance than small icons, which need a lower one to keep
+    /// their curves smooth.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bevy_prototype_lyon::prelude::*;
+    ///
+    /// let mode = DrawMode::Outlined {
+    ///     fill_options: FillOptions::default(),
+    ///     outline_options: StrokeOptions::default(),
+    /// }
+    /// .with_tolerance(0.05);
+    /// assert_eq!(mode.tolerance(), 0.05);
+    /// ```
+    #[must_use]
+    pub fn with_tolerance(self, tolerance: f32) -> Self {
+        match self {
+            Self::Fill(options) => Self::Fill(options.with_tolerance(tolerance)),
+            Self::Stroke(options) => Self::Stroke(options.with_tolerance(tolerance)),
+            Self::Outlined {
+                fill_options,
+                outline_options,
+            } => Self::Outlined {
+                fill_options: fill_options.with_tolerance(tolerance),
+                outline_options: outline_options.with_tolerance(tolerance),
+            },
+        }
+    }
+
+    /// Returns the tessellation tolerance of the draw mode. For
+    /// [`DrawMode::Outlined`], it is the smallest of the fill and outline
+    /// tolerances.
+    #[must_use]
+    pub fn tolerance(&self) -> f32 {
+        match self {
+            Self::Fill(options) => options.tolerance,
+            Self::Stroke(options) => options.tolerance,
+            Self::Outlined {
+                fill_options,
+                outline_options,
+            } => fill_options.tolerance.min(outline_options.tolerance),
+        }
+    }
+
+    /// Returns the fill rule of the draw mode, or `None` for
+    /// [`DrawMode::Stroke`].
+    #[must_use]
+    pub const fn fill_rule(&self) -> Option<FillRule> {
+        match self {
+            Self::Fill(options)
+            | Self::Outlined {
+                fill_options: options,
+                ..
+            } => Some(options.fill_rule),
+            Self::Stroke(_) => None,
+        }
+    }
+}
+
+/// A resource holding the default fill and stroke options of a project, to
+/// draw shapes with a consistent style.
+///
+/// The [`ShapePlugin`](crate::plugin::ShapePlugin) adds it with the lyon
+/// defaults, unless it has already been inserted. Shapes with a
+/// [`DefaultDrawMode`] component take their [`DrawMode`] from it, and are
+/// drawn again when it changes, so changing the style of the project only
+/// requires changing the resource. Its methods also return the draw modes, for
+/// the shapes given one explicitly.
+///
+/// # Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_prototype_lyon::prelude::*;
+///
+/// fn main() {
+///     App::build()
+///         .add_plugins(DefaultPlugins)
+///         .insert_resource(ShapeSettings {
+///             fill_options: FillOptions::default().with_tolerance(0.05),
+///             stroke_options: StrokeOptions::default()
+///                 .with_line_width(2.0)
+///                 .with_line_cap(LineCap::Round),
+///         })
+///         .add_plugin(ShapePlugin)
+///         .add_startup_system(setup.system());
+/// }
+///
+/// fn setup(mut commands: Commands, settings: Res<ShapeSettings>) {
+///     let circle = shapes::Circle::default();
+///     commands
+///         .spawn_bundle(GeometryBuilder::build_as(
+///             &circle,
+///             ShapeColors::new(Color::BLACK),
+///             settings.stroke(),
+///             Transform::default(),
+///         ))
+///         .insert(DefaultDrawMode::Stroke);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShapeSettings {
+    /// The options of [`fill`](Self::fill), and of the fill of
+    /// [`outlined`](Self::outlined).
+    pub fill_options: FillOptions,
+    /// The options of [`stroke`](Self::stroke), and of the outline of
+    /// [`outlined`](Self::outlined).
+    pub stroke_options: StrokeOptions,
+}
+
+impl ShapeSettings {
+    /// Returns a [`DrawMode::Fill`] with the default fill options.
+    #[must_use]
+    pub const fn fill(&self) -> DrawMode {
+        DrawMode::Fill(self.fill_options)
+    }
+
+    /// Returns a [`DrawMode::Stroke`] with the default stroke options.
+    #[must_use]
+    pub const fn stroke(&self) -> DrawMode {
+        DrawMode::Stroke(self.stroke_options)
+    }
+
+    /// Returns a [`DrawMode::Outlined`] with the default fill and stroke
+    /// options.
+    #[must_use]
+    pub const fn outlined(&self) -> DrawMode {
+        DrawMode::Outlined {
+            fill_options: self.fill_options,
+            outline_options: self.stroke_options,
+        }
+    }
+
+    /// Returns the draw mode of the given kind with the default options.
+    #[must_use]
+    pub const fn draw_mode(&self, mode: DefaultDrawMode) -> DrawMode {
+        match mode {
+            DefaultDrawMode::Fill => self.fill(),
+            DefaultDrawMode::Stroke => self.stroke(),
+            DefaultDrawMode::Outlined => self.outlined(),
+        }
+    }
+}
+
+impl Default for ShapeSettings {
+    fn default() -> Self {
+        Self {
+            fill_options: FillOptions::default(),
+            stroke_options: StrokeOptions::default(),
+        }
+    }
+}
+
+/// Gives a shape the draw mode of the given kind from the [`ShapeSettings`].
+///
+/// Insert it as a component on an entity spawned with a
+/// [`ShapeBundle`](crate::entity::ShapeBundle). Its [`DrawMode`] is then
+/// replaced in `POST_UPDATE`, and again every time the settings change, so
+/// the draw mode given to the bundle does not matter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DefaultDrawMode {
+    /// [`ShapeSettings::fill`].
+    Fill,
+    /// [`ShapeSettings::stroke`].
+    Stroke,
+    /// [`ShapeSettings::outlined`].
+    Outlined,
+}
+
+/// A builder of [`FillOptions`] for the common cases, which converts into the
+/// options or into a [`DrawMode::Fill`].
+///
+/// # Example
+///
+/// ```
+/// use bevy_prototype_lyon::prelude::*;
+///
+/// let mode: DrawMode = FillStyle::new().even_odd().into();
+/// assert_eq!(mode.fill_rule(), Some(FillRule::EvenOdd));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FillStyle(FillOptions);
+
+impl FillStyle {
+    /// Creates a fill style with the default options.
+    #[must_use]
+    pub fn new() -> Self {
+        Self(FillOptions::default())
+    }
+
+    /// Uses [`FillRule::EvenOdd`], so every sub-path inside another one
+    /// becomes a hole.
+    #[must_use]
+    pub fn even_odd(self) -> Self {
+        Self(self.0.with_fill_rule(FillRule::EvenOdd))
+    }
+
+    /// Sets the tessellation tolerance.
+    #[must_use]
+    pub fn tolerance(self, tolerance: f32) -> Self {
+        Self(self.0.with_tolerance(tolerance))
+    }
+}
+
+impl Default for FillStyle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<FillStyle> for FillOptions {
+    fn from(style: FillStyle) -> Self {
+        style.0
+    }
+}
+
+impl From<FillStyle> for DrawMode {
+    fn from(style: FillStyle) -> Self {
+        Self::Fill(style.0)
+    }
+}
+
+/// A builder of [`StrokeOptions`] for the common cases, which converts into
+/// the options or into a [`DrawMode::Stroke`]. Together with a [`FillStyle`],
+/// it converts into a [`DrawMode::Outlined`].
+///
+/// # Example
+///
+/// ```
+/// use bevy_prototype_lyon::prelude::*;
+///
+/// let mode: DrawMode = StrokeStyle::new(3.0).round_caps().round_joins().into();
+/// let outlined: DrawMode = (FillStyle::new(), StrokeStyle::new(1.0).miter_limit(2.0)).into();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StrokeStyle(StrokeOptions);
+
+impl StrokeStyle {
+    /// Creates a stroke style with the given line width, and the default
+    /// options otherwise.
+    #[must_use]
+    pub fn new(width: f32) -> Self {
+        Self(StrokeOptions::default().with_line_width(width))
+    }
+
+    /// Ends the lines with round caps.
+    #[must_use]
+    pub fn round_caps(self) -> Self {
+        Self(self.0.with_line_cap(LineCap::Round))
+    }
+
+    /// Ends the lines with square caps, extending them by half their width.
+    #[must_use]
+    pub fn square_caps(self) -> Self {
+        Self(self.0.with_line_cap(LineCap::Square))
+    }
+
+    /// Joins the segments with round corners.
+    #[must_use]
+    pub fn round_joins(self) -> Self {
+        Self(self.0.with_line_join(LineJoin::Round))
+    }
+
+    /// Joins the segments with beveled corners.
+    #[must_use]
+    pub fn bevel_joins(self) -> Self {
+        Self(self.0.with_line_join(LineJoin::Bevel))
+    }
+
+    /// Sets the miter limit, beyond which sharp miter joins are beveled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the limit is lower than
+    /// [`StrokeOptions::MINIMUM_MITER_LIMIT`].
+    #[must_use]
+    pub fn miter_limit(self, limit: f32) -> Self {
+        Self(self.0.with_miter_limit(limit))
+    }
+
+    /// Sets the tessellation tolerance.
+    #[must_use]
+    pub fn tolerance(self, tolerance: f32) -> Self {
+        Self(self.0.with_tolerance(tolerance))
+    }
+}
+
+impl From<StrokeStyle> for StrokeOptions {
+    fn from(style: StrokeStyle) -> Self {
+        style.0
+    }
+}
+
+impl From<StrokeStyle> for DrawMode {
+    fn from(style: StrokeStyle) -> Self {
+        Self::Stroke(style.0)
+    }
+}
+
+impl From<(FillStyle, StrokeStyle)> for DrawMode {
+    fn from((fill, outline): (FillStyle, StrokeStyle)) -> Self {
+        Self::Outlined {
+            fill_options: fill.0,
+            outline_options: outline.0,
+        }
+    }
+}
+
 /// A locally defined [`std::convert::Into`] surrogate to overcome orphan rules.
+///
+/// It converts between the Bevy [`Vec2`] and the lyon [`Point`] and
+/// [`Vector`], so code using this crate can stay in Bevy math types.
+///
+/// # Example
+///
+/// ```
+/// use bevy::math::Vec2;
+/// use bevy_prototype_lyon::prelude::*;
+/// use lyon_tessellation::math::Point;
+///
+/// let point: Point = Vec2::new(1.0, 2.0).convert();
+/// let back: Vec2 = point.convert();
+/// assert_eq!(back, Vec2::new(1.0, 2.0));
+/// ```
 pub trait Convert<T>: Sized {
     /// Converts the value to `T`.
     fn convert(self) -> T;
@@ -45,3 +416,36 @@ impl Convert<Vector> for Vec2 {
         Vector::new(self.x, self.y)
     }
 }
+
+impl Convert<Vec2> for Vector {
+    fn convert(self) -> Vec2 {
+        Vec2::new(self.x, self.y)
+    }
+}
+
+/// A small, seedable pseudo-random number generator based on SplitMix64.
+/// Used by procedural shapes, so that the same seed always gives the same
+/// shape regardless of the platform.
+pub(crate) struct SplitMix64(u64);
+
+impl SplitMix64 {
+    /// Creates a new generator with the given seed.
+    pub(crate) const fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    /// Returns the next random `u64`.
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a random `f32` in the range `[-1.0, 1.0)`.
+    pub(crate) fn next_signed(&mut self) -> f32 {
+        let unit = (self.next_u64() >> 40) as f32 / (1_u64 << 24) as f32;
+        unit.mul_add(2.0, -1.0)
+    }
+}